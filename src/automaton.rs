@@ -0,0 +1,303 @@
+use core::{fmt::Display, mem, str::FromStr};
+
+use crate::{DynamicGrid, Neighborhood};
+
+/// Behavior at the edges of the grid, for cellular automata
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Boundary {
+    /// Cells on the edges have fewer neighbors
+    Bounded,
+    /// The grid wraps around: cells on an edge are neighbors of the cells on the opposite edge
+    Wrapping,
+}
+
+/// Neighbors of a cell, given to the rule of [`DynamicGrid::step_automaton`]
+#[derive(Debug)]
+pub struct Neighbors<'a, T> {
+    grid: &'a DynamicGrid<T>,
+    x: usize,
+    y: usize,
+    neighborhood: Neighborhood,
+    boundary: Boundary,
+}
+
+/// Rule of a "life-like" cellular automaton, such as Conway's game of life
+///
+/// It can be parsed from the "B/S" notation (for example `"B3/S23"` for Conway's game of life).
+///
+/// # Example
+///
+/// ```
+/// # use cell_grid::LifeRule;
+/// let rule: LifeRule = "B36/S23".parse().unwrap();
+/// assert!(rule.next(false, 6));
+/// assert!(rule.next(true, 2));
+/// assert!(!rule.next(true, 4));
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct LifeRule {
+    birth: u16,
+    survival: u16,
+}
+
+/// Error returned when parsing an invalid [`LifeRule`]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ParseLifeRuleError;
+
+impl<T> DynamicGrid<T> {
+    /// Compute the next generation of a cellular automaton
+    ///
+    /// The `rule` is called for every cell with the current value of the cell and its neighbors, and returns the next value.
+    /// The next generation is written into `buffer` (which is resized as needed), then swapped with this grid.
+    /// After the call, this grid contains the next generation, and `buffer` contains the previous one.
+    /// Reusing the same buffer for every step avoids allocations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{Boundary, DynamicGrid, Neighborhood};
+    /// let mut grid = DynamicGrid::new_from_iter(3, [0, 0, 0, 0, 1, 0, 0, 0, 0]).unwrap();
+    /// let mut buffer = DynamicGrid::new();
+    /// grid.step_automaton(&mut buffer, Neighborhood::VonNeumann, Boundary::Bounded, |cell, neighbors| {
+    ///     cell + neighbors.iter().sum::<i32>()
+    /// });
+    /// assert_eq!(grid.cells().copied().collect::<Vec<_>>(), [0, 1, 0, 1, 1, 1, 0, 1, 0]);
+    /// ```
+    pub fn step_automaton(
+        &mut self,
+        buffer: &mut DynamicGrid<T>,
+        neighborhood: Neighborhood,
+        boundary: Boundary,
+        mut rule: impl FnMut(&T, Neighbors<'_, T>) -> T,
+    ) {
+        buffer.cells.clear();
+        buffer.width = self.width;
+        let grid = &*self;
+        buffer.cells.extend(
+            grid.cells
+                .iter()
+                .enumerate()
+                .map(|(index, cell)| rule(cell, grid.neighbors_of(index, neighborhood, boundary))),
+        );
+        mem::swap(self, buffer);
+    }
+
+    /// Parallel version of [`Self::step_automaton`]
+    #[cfg(feature = "rayon")]
+    pub fn par_step_automaton(
+        &mut self,
+        buffer: &mut DynamicGrid<T>,
+        neighborhood: Neighborhood,
+        boundary: Boundary,
+        rule: impl Fn(&T, Neighbors<'_, T>) -> T + Sync,
+    ) where
+        T: Send + Sync,
+    {
+        use rayon::prelude::*;
+        buffer.width = self.width;
+        let grid = &*self;
+        grid.cells
+            .par_iter()
+            .enumerate()
+            .map(|(index, cell)| rule(cell, grid.neighbors_of(index, neighborhood, boundary)))
+            .collect_into_vec(&mut buffer.cells);
+        mem::swap(self, buffer);
+    }
+
+    /// Compute the next generation of a block cellular automaton using the Margolus neighborhood
+    ///
+    /// The grid is partitioned in blocks of 2 by 2 cells, and the `rule` is called with the cells of each block
+    /// in the order: top-left, top-right, bottom-left, bottom-right. It returns the next values of the block, in the same order.
+    ///
+    /// The partition is shifted by one cell in both directions when `odd` is `true`.
+    /// Alternate `odd` between steps for the cells to interact with all their neighbors.
+    ///
+    /// Incomplete blocks on the edges are left unchanged, unless the `boundary` is [`Boundary::Wrapping`]
+    /// and the size of the grid along that axis is even.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{Boundary, DynamicGrid};
+    /// let mut grid = DynamicGrid::new_from_iter(2, [1, 2, 3, 4]).unwrap();
+    /// grid.step_margolus(false, Boundary::Bounded, |[a, b, c, d]| [*d, *c, *b, *a]);
+    /// assert_eq!(grid.cells().copied().collect::<Vec<_>>(), [4, 3, 2, 1]);
+    /// ```
+    pub fn step_margolus(
+        &mut self,
+        odd: bool,
+        boundary: Boundary,
+        mut rule: impl FnMut([&T; 4]) -> [T; 4],
+    ) {
+        let (width, height) = (self.width, self.height_or_zero());
+        let wraps = |size: usize| boundary == Boundary::Wrapping && size % 2 == 0;
+        let start = usize::from(odd);
+        for y in (start..height).step_by(2) {
+            if y + 1 >= height && !wraps(height) {
+                continue;
+            }
+            let below = (y + 1) % height;
+            for x in (start..width).step_by(2) {
+                if x + 1 >= width && !wraps(width) {
+                    continue;
+                }
+                let right = (x + 1) % width;
+                let block = [
+                    y * width + x,
+                    y * width + right,
+                    below * width + x,
+                    below * width + right,
+                ];
+                let next = rule(block.map(|i| &self.cells[i]));
+                for (index, value) in block.into_iter().zip(next) {
+                    self.cells[index] = value;
+                }
+            }
+        }
+    }
+
+    fn neighbors_of(
+        &self,
+        index: usize,
+        neighborhood: Neighborhood,
+        boundary: Boundary,
+    ) -> Neighbors<'_, T> {
+        let (x, y) = Self::index_to_coord(index, self.width);
+        Neighbors {
+            grid: self,
+            x,
+            y,
+            neighborhood,
+            boundary,
+        }
+    }
+}
+
+impl DynamicGrid<bool> {
+    /// Compute the next generation of a life-like cellular automaton, where `true` is alive
+    ///
+    /// See [`Self::step_automaton`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{Boundary, DynamicGrid, LifeRule};
+    /// let mut grid = DynamicGrid::new_with(5, 5, |x, y| y == 2 && (1..=3).contains(&x));
+    /// let mut buffer = DynamicGrid::new();
+    /// grid.step_life(&mut buffer, LifeRule::CONWAY, Boundary::Bounded);
+    /// assert_eq!(grid, DynamicGrid::new_with(5, 5, |x, y| x == 2 && (1..=3).contains(&y)));
+    /// ```
+    pub fn step_life(
+        &mut self,
+        buffer: &mut DynamicGrid<bool>,
+        rule: LifeRule,
+        boundary: Boundary,
+    ) {
+        self.step_automaton(buffer, Neighborhood::Moore, boundary, |alive, neighbors| {
+            rule.next(*alive, neighbors.count(|c| *c))
+        });
+    }
+}
+
+impl<'a, T> Neighbors<'a, T> {
+    /// Returns the cell at the given offset from the current cell
+    ///
+    /// The offset is not restricted to the neighborhood.
+    /// Returns `None` if the offset is out of bounds and the boundary is [`Boundary::Bounded`].
+    #[must_use]
+    pub fn get(&self, dx: isize, dy: isize) -> Option<&'a T> {
+        let height = self.grid.height_or_zero();
+        let x = shift(self.x, dx, self.grid.width, self.boundary)?;
+        let y = shift(self.y, dy, height, self.boundary)?;
+        self.grid.get(x, y)
+    }
+
+    /// Returns an iterator over the neighbor cells
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.neighborhood
+            .offsets()
+            .iter()
+            .filter_map(move |&(dx, dy)| self.get(isize::from(dx), isize::from(dy)))
+    }
+
+    /// Returns the number of neighbor cells matching the `predicate`
+    pub fn count(&self, mut predicate: impl FnMut(&T) -> bool) -> usize {
+        self.iter().filter(|c| predicate(c)).count()
+    }
+}
+
+fn shift(value: usize, delta: isize, size: usize, boundary: Boundary) -> Option<usize> {
+    let value = isize::try_from(value).ok()?;
+    match boundary {
+        Boundary::Bounded => usize::try_from(value.checked_add(delta)?)
+            .ok()
+            .filter(|v| *v < size),
+        Boundary::Wrapping => {
+            let size = isize::try_from(size).ok()?;
+            usize::try_from(value.checked_add(delta.rem_euclid(size))?.rem_euclid(size)).ok()
+        }
+    }
+}
+
+impl LifeRule {
+    /// Conway's game of life (`B3/S23`)
+    pub const CONWAY: Self = Self {
+        birth: 1 << 3,
+        survival: (1 << 2) | (1 << 3),
+    };
+
+    /// Returns whether a cell is alive in the next generation
+    #[must_use]
+    pub fn next(self, alive: bool, alive_neighbors: usize) -> bool {
+        let set = if alive { self.survival } else { self.birth };
+        alive_neighbors <= 8 && set & (1 << alive_neighbors) != 0
+    }
+}
+
+impl FromStr for LifeRule {
+    type Err = ParseLifeRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rule = Self {
+            birth: 0,
+            survival: 0,
+        };
+        let mut seen = (false, false);
+        for part in s.split('/') {
+            let mut chars = part.chars();
+            let set = match chars.next() {
+                Some('B' | 'b') if !seen.0 => {
+                    seen.0 = true;
+                    &mut rule.birth
+                }
+                Some('S' | 's') if !seen.1 => {
+                    seen.1 = true;
+                    &mut rule.survival
+                }
+                _ => return Err(ParseLifeRuleError),
+            };
+            for c in chars {
+                match c.to_digit(10) {
+                    Some(n) if n <= 8 => *set |= 1 << n,
+                    _ => return Err(ParseLifeRuleError),
+                }
+            }
+        }
+        if seen == (true, true) {
+            Ok(rule)
+        } else {
+            Err(ParseLifeRuleError)
+        }
+    }
+}
+
+impl Display for ParseLifeRuleError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Invalid life rule, expected a rule such as \"B3/S23\"")
+    }
+}
+
+#[rustversion::since(1.81)]
+impl core::error::Error for ParseLifeRuleError {}
@@ -0,0 +1,362 @@
+use core::{fmt::Display, iter};
+
+use alloc::vec::Vec;
+
+use crate::{DynamicGrid, IncompatibleRowSize};
+
+const MAGIC: [u8; 4] = *b"CGRD";
+const VERSION: u8 = 1;
+const HEADER_SIZE: usize = 15;
+/// Maximum size of the cells decoded from run-length encoded data, in bytes
+const MAX_DECODED_SIZE: usize = 1 << 30;
+
+/// Cell that can be stored in the binary format of [`DynamicGrid::to_bytes`]
+///
+/// Implemented for all fixed-size primitive integer types.
+pub trait BinaryCell: Copy + Eq {
+    /// Size of a cell, in bytes
+    const SIZE: usize;
+
+    /// The value against which the first cell is computed by [`Codec::Delta`]
+    const ZERO: Self;
+
+    /// Append the little-endian representation of the cell to `bytes`
+    fn write_le(self, bytes: &mut Vec<u8>);
+
+    /// Read a cell from its little-endian representation
+    ///
+    /// # Panics
+    ///
+    /// May panic if the length of `bytes` is not [`Self::SIZE`]
+    fn read_le(bytes: &[u8]) -> Self;
+
+    /// Returns the difference between `self` and `previous`, wrapping around on overflow
+    #[must_use]
+    fn delta(self, previous: Self) -> Self;
+
+    /// Returns the cell whose [`delta`](Self::delta) with `self` is `delta`
+    #[must_use]
+    fn add_delta(self, delta: Self) -> Self;
+}
+
+macro_rules! impl_binary_cell {
+    ($($t:ty),*) => {
+        $(impl BinaryCell for $t {
+            const SIZE: usize = core::mem::size_of::<$t>();
+            const ZERO: Self = 0;
+
+            fn write_le(self, bytes: &mut Vec<u8>) {
+                bytes.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn read_le(bytes: &[u8]) -> Self {
+                let mut array = [0; core::mem::size_of::<$t>()];
+                array.copy_from_slice(bytes);
+                Self::from_le_bytes(array)
+            }
+
+            fn delta(self, previous: Self) -> Self {
+                self.wrapping_sub(previous)
+            }
+
+            fn add_delta(self, delta: Self) -> Self {
+                self.wrapping_add(delta)
+            }
+        })*
+    };
+}
+
+impl_binary_cell!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// Encoding of the cells in the binary format of [`DynamicGrid::to_bytes`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Codec {
+    /// Every cell is stored as is
+    Raw,
+    /// Runs of equal cells are stored once, preceded by their length
+    Rle,
+    /// Every cell is replaced by its difference with the previous one, and the differences are run-length encoded
+    ///
+    /// It is the most compact codec for smooth gradients, such as height maps.
+    Delta,
+}
+
+/// Error returned by [`DynamicGrid::from_bytes`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct DecodeBinaryError {
+    kind: DecodeBinaryErrorKind,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum DecodeBinaryErrorKind {
+    InvalidMagic,
+    UnsupportedVersion(u8),
+    UnknownCodec(u8),
+    CellSizeMismatch { expected: usize, found: u8 },
+    TooLarge,
+    InvalidData,
+}
+
+impl Codec {
+    fn id(self) -> u8 {
+        match self {
+            Self::Raw => 0,
+            Self::Rle => 1,
+            Self::Delta => 2,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::Raw),
+            1 => Some(Self::Rle),
+            2 => Some(Self::Delta),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Eq + Clone> DynamicGrid<T> {
+    /// Run-length encode the cells, in row-major order
+    ///
+    /// Returns the runs of equal cells, as pairs of run length and cell value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_from_iter(3, "aab bb".chars()).unwrap();
+    /// let runs = grid.encode_rle();
+    /// assert_eq!(runs, [(2, 'a'), (1, 'b'), (1, ' '), (2, 'b')]);
+    /// assert_eq!(DynamicGrid::decode_rle(3, runs).unwrap(), grid);
+    /// ```
+    #[must_use]
+    pub fn encode_rle(&self) -> Vec<(usize, T)> {
+        let mut runs: Vec<(usize, T)> = Vec::new();
+        for cell in &self.cells {
+            match runs.last_mut() {
+                Some((length, value)) if value == cell => *length += 1,
+                _ => runs.push((1, cell.clone())),
+            }
+        }
+        runs
+    }
+}
+
+impl<T: Clone> DynamicGrid<T> {
+    /// Create a grid of the given `width` from runs of equal cells, as returned by [`DynamicGrid::encode_rle`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IncompatibleRowSize`] if the total length of the runs is not a multiple of `width`
+    pub fn decode_rle(
+        width: usize,
+        runs: impl IntoIterator<Item = (usize, T)>,
+    ) -> Result<Self, IncompatibleRowSize> {
+        Self::new_from_iter(
+            width,
+            runs.into_iter()
+                .flat_map(|(length, value)| iter::repeat(value).take(length)),
+        )
+    }
+}
+
+impl<T: BinaryCell> DynamicGrid<T> {
+    /// Encode the grid in a self-describing little-endian binary format
+    ///
+    /// The format starts with a header made of:
+    ///
+    /// * the magic number `CGRD`
+    /// * the version of the format, on 1 byte (currently `1`)
+    /// * the id of the [`Codec`], on 1 byte (`0` for raw, `1` for RLE and `2` for delta)
+    /// * the size of a cell in bytes, on 1 byte
+    /// * the width and the height, as 4-byte unsigned integers
+    ///
+    /// It is followed by the cells, encoded by `codec`.
+    /// Run lengths are stored as LEB128 variable-length integers, followed by the cell value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width or the height is greater than [`u32::MAX`], or if [`BinaryCell::SIZE`] is greater than 255
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{Codec, DynamicGrid};
+    /// let grid = DynamicGrid::new_with(100, 100, |x, _| u16::from(x > 50));
+    /// let bytes = grid.to_bytes(Codec::Rle);
+    /// assert!(bytes.len() < 1000);
+    /// assert_eq!(DynamicGrid::from_bytes(&bytes).unwrap(), grid);
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self, codec: Codec) -> Vec<u8> {
+        let width = u32::try_from(self.width).expect("the width should fit in a u32");
+        let height = u32::try_from(self.height_or_zero()).expect("the height should fit in a u32");
+        let cell_size = u8::try_from(T::SIZE).expect("the cell size should fit in a u8");
+        let mut bytes = Vec::with_capacity(HEADER_SIZE);
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&[VERSION, codec.id(), cell_size]);
+        bytes.extend_from_slice(&width.to_le_bytes());
+        bytes.extend_from_slice(&height.to_le_bytes());
+        match codec {
+            Codec::Raw => {
+                for cell in &self.cells {
+                    cell.write_le(&mut bytes);
+                }
+            }
+            Codec::Rle => write_runs(&mut bytes, self.cells.iter().copied()),
+            Codec::Delta => {
+                let mut previous = T::ZERO;
+                let deltas = self.cells.iter().map(|&cell| {
+                    let delta = cell.delta(previous);
+                    previous = cell;
+                    delta
+                });
+                write_runs(&mut bytes, deltas);
+            }
+        }
+        bytes
+    }
+
+    /// Decode a grid from the binary format of [`DynamicGrid::to_bytes`]
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeBinaryError`] if the bytes are not in the expected format,
+    /// if the version or the codec is not supported, if the cell size does not match `T`,
+    /// or if the cells do not match the size of the grid.
+    ///
+    /// With the [`Codec::Rle`] and [`Codec::Delta`] codecs, the decoded cells may not take more than 2<sup>30</sup> bytes,
+    /// so that a small input cannot claim an arbitrarily large grid.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeBinaryError> {
+        if bytes.len() < HEADER_SIZE || bytes[..4] != MAGIC {
+            return Err(DecodeBinaryError::new(DecodeBinaryErrorKind::InvalidMagic));
+        }
+        if bytes[4] != VERSION {
+            return Err(DecodeBinaryError::new(
+                DecodeBinaryErrorKind::UnsupportedVersion(bytes[4]),
+            ));
+        }
+        let codec = Codec::from_id(bytes[5])
+            .ok_or_else(|| DecodeBinaryError::new(DecodeBinaryErrorKind::UnknownCodec(bytes[5])))?;
+        if usize::from(bytes[6]) != T::SIZE {
+            return Err(DecodeBinaryError::new(
+                DecodeBinaryErrorKind::CellSizeMismatch {
+                    expected: T::SIZE,
+                    found: bytes[6],
+                },
+            ));
+        }
+        let invalid = || DecodeBinaryError::new(DecodeBinaryErrorKind::InvalidData);
+        let width = read_u32(&bytes[7..11]).ok_or_else(invalid)?;
+        let height = read_u32(&bytes[11..15]).ok_or_else(invalid)?;
+        let len = width.checked_mul(height).ok_or_else(invalid)?;
+        let payload = &bytes[HEADER_SIZE..];
+        if codec != Codec::Raw
+            && len
+                .checked_mul(T::SIZE)
+                .map_or(true, |size| size > MAX_DECODED_SIZE)
+        {
+            return Err(DecodeBinaryError::new(DecodeBinaryErrorKind::TooLarge));
+        }
+        let cells = match codec {
+            Codec::Raw => {
+                if Some(payload.len()) != len.checked_mul(T::SIZE) {
+                    return Err(invalid());
+                }
+                payload.chunks_exact(T::SIZE).map(T::read_le).collect()
+            }
+            Codec::Rle => read_runs(payload, len).ok_or_else(invalid)?,
+            Codec::Delta => {
+                let mut cells = read_runs::<T>(payload, len).ok_or_else(invalid)?;
+                let mut previous = T::ZERO;
+                for cell in &mut cells {
+                    *cell = previous.add_delta(*cell);
+                    previous = *cell;
+                }
+                cells
+            }
+        };
+        Ok(Self { cells, width })
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> Option<usize> {
+    let value = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    usize::try_from(value).ok()
+}
+
+fn write_runs<T: BinaryCell>(bytes: &mut Vec<u8>, cells: impl Iterator<Item = T>) {
+    let mut cells = cells.peekable();
+    while let Some(cell) = cells.next() {
+        let mut length: u64 = 1;
+        while cells.next_if_eq(&cell).is_some() {
+            length += 1;
+        }
+        while length >= 0x80 {
+            bytes.push(0x80 | (length & 0x7f).to_le_bytes()[0]);
+            length >>= 7;
+        }
+        bytes.push(length.to_le_bytes()[0]);
+        cell.write_le(bytes);
+    }
+}
+
+fn read_runs<T: BinaryCell>(mut payload: &[u8], len: usize) -> Option<Vec<T>> {
+    let mut cells = Vec::new();
+    while !payload.is_empty() {
+        let mut length: usize = 0;
+        let mut shift = 0;
+        loop {
+            let (&byte, rest) = payload.split_first()?;
+            payload = rest;
+            let bits = usize::from(byte & 0x7f);
+            length |= bits
+                .checked_shl(shift)
+                .filter(|value| value >> shift == bits)?;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+        if length == 0 || payload.len() < T::SIZE || length > len - cells.len() {
+            return None;
+        }
+        let (cell, rest) = payload.split_at(T::SIZE);
+        payload = rest;
+        cells.try_reserve(length).ok()?;
+        cells.extend(iter::repeat(T::read_le(cell)).take(length));
+    }
+    (cells.len() == len).then(|| cells)
+}
+
+impl DecodeBinaryError {
+    fn new(kind: DecodeBinaryErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl Display for DecodeBinaryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.kind {
+            DecodeBinaryErrorKind::InvalidMagic => write!(f, "Not a binary grid"),
+            DecodeBinaryErrorKind::UnsupportedVersion(version) => {
+                write!(f, "Unsupported binary grid version: {version}")
+            }
+            DecodeBinaryErrorKind::UnknownCodec(codec) => write!(f, "Unknown codec id: {codec}"),
+            DecodeBinaryErrorKind::CellSizeMismatch { expected, found } => write!(
+                f,
+                "Expected cells of {expected} bytes, found cells of {found} bytes"
+            ),
+            DecodeBinaryErrorKind::TooLarge => write!(f, "The grid is too large to be decoded"),
+            DecodeBinaryErrorKind::InvalidData => {
+                write!(f, "The cells do not match the size of the grid")
+            }
+        }
+    }
+}
+
+#[rustversion::since(1.81)]
+impl core::error::Error for DecodeBinaryError {}
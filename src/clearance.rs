@@ -0,0 +1,156 @@
+use alloc::vec::Vec;
+
+use crate::{Cost, DynamicGrid, Neighborhood};
+
+impl<T> DynamicGrid<T> {
+    /// Compute the true clearance of every cell
+    ///
+    /// The clearance of a cell is the size of the largest square of `walkable` cells having this cell as top-left corner.
+    /// A unit of size `n` (covering `n` by `n` cells) can stand at a cell if its clearance is at least `n`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_from_iter(3, [
+    ///     true, true, true,
+    ///     true, true, false,
+    ///     true, true, true,
+    /// ]).unwrap();
+    /// let clearance = grid.clearance(|c| *c);
+    /// assert_eq!(clearance.get(0, 0), Some(&2));
+    /// assert_eq!(clearance.get(1, 0), Some(&1));
+    /// assert_eq!(clearance.get(2, 1), Some(&0));
+    /// ```
+    #[must_use]
+    pub fn clearance(&self, mut walkable: impl FnMut(&T) -> bool) -> DynamicGrid<usize> {
+        let mut clearance = DynamicGrid {
+            cells: self
+                .cells
+                .iter()
+                .map(|c| usize::from(walkable(c)))
+                .collect(),
+            width: self.width,
+        };
+        if !self.cells.is_empty() {
+            let height = self.height();
+            clearance.propagate_clearance(self.width, height);
+        }
+        clearance
+    }
+
+    /// Update a `clearance` map after the cells in the given rectangle have changed
+    ///
+    /// The `walkable` function must be the same as the one used to create the `clearance` map.
+    /// Only the cells above and on the left of the rectangle are recomputed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of `clearance` does not match the size of this grid
+    pub fn update_clearance(
+        &self,
+        clearance: &mut DynamicGrid<usize>,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        mut walkable: impl FnMut(&T) -> bool,
+    ) {
+        assert!(
+            clearance.width == self.width && clearance.cells.len() == self.cells.len(),
+            "the clearance map size does not match the grid size"
+        );
+        if self.cells.is_empty() {
+            return;
+        }
+        let max_x = x.saturating_add(width).min(self.width);
+        let max_y = y.saturating_add(height).min(self.height());
+        for y in 0..max_y {
+            for x in 0..max_x {
+                let index = y * self.width + x;
+                clearance.cells[index] = usize::from(walkable(&self.cells[index]));
+            }
+        }
+        clearance.propagate_clearance(max_x, max_y);
+    }
+
+    /// Compute a dijkstra map for units covering `size` by `size` cells
+    ///
+    /// Units are located by their top-left cell, which can only be entered if all the cells covered by the unit can be entered.
+    /// The cost of moving is the `cost` of entering the top-left cell.
+    ///
+    /// See [`Self::dijkstra_map`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, Neighborhood};
+    /// let grid = DynamicGrid::new_from_iter(3, [
+    ///     '.', '.', '.',
+    ///     '.', '#', '.',
+    ///     '.', '.', '.',
+    /// ]).unwrap();
+    /// let cost = |c: &char| (*c == '.').then(|| 1);
+    /// let small = grid.dijkstra_map_for_size([(0, 0)], 1, Neighborhood::VonNeumann, cost);
+    /// let large = grid.dijkstra_map_for_size([(0, 0)], 2, Neighborhood::VonNeumann, cost);
+    /// assert_eq!(small.get(2, 2), Some(&Some(4)));
+    /// assert_eq!(large.get(1, 1), Some(&None));
+    /// ```
+    #[must_use]
+    pub fn dijkstra_map_for_size<C: Cost>(
+        &self,
+        goals: impl IntoIterator<Item = (usize, usize)>,
+        size: usize,
+        neighborhood: Neighborhood,
+        cost: impl FnMut(&T) -> Option<C>,
+    ) -> DynamicGrid<Option<C>> {
+        let costs = DynamicGrid {
+            cells: self.cells.iter().map(cost).collect::<Vec<_>>(),
+            width: self.width,
+        };
+        let clearance = costs.clearance(Option::is_some);
+        let entry_costs = DynamicGrid {
+            cells: costs
+                .cells
+                .into_iter()
+                .zip(clearance.cells)
+                .map(|(cost, clearance)| cost.filter(|_| clearance >= size))
+                .collect::<Vec<_>>(),
+            width: self.width,
+        };
+        entry_costs.dijkstra_map(goals, neighborhood, |c| *c)
+    }
+}
+
+impl DynamicGrid<usize> {
+    /// Propagate clearance (from bottom-right to top-left) in the rectangle from `(0, 0)` to `(max_x, max_y)` (exclusive)
+    ///
+    /// Cells must contain `1` if they are walkable and `0` otherwise.
+    fn propagate_clearance(&mut self, max_x: usize, max_y: usize) {
+        let height = self.height();
+        for y in (0..max_y).rev() {
+            for x in (0..max_x).rev() {
+                let index = y * self.width + x;
+                if self.cells[index] == 0 {
+                    continue;
+                }
+                let right = if x + 1 < self.width {
+                    self.cells[index + 1]
+                } else {
+                    0
+                };
+                let below = if y + 1 < height {
+                    self.cells[index + self.width]
+                } else {
+                    0
+                };
+                let diagonal = if x + 1 < self.width && y + 1 < height {
+                    self.cells[index + self.width + 1]
+                } else {
+                    0
+                };
+                self.cells[index] = 1 + right.min(below).min(diagonal);
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! Collision resolution of axis-aligned boxes moving against solid cells
+//!
+//! Positions are in world space (x = right, y = down), where the cell at col `x` and row `y` covers the area
+//! from `[x * cell_size[0], y * cell_size[1]]` to `[(x + 1) * cell_size[0], (y + 1) * cell_size[1]]`.
+//!
+//! # Example
+//!
+//! ```
+//! # use cell_grid::{DynamicGrid, collision::{Aabb, CollisionShape, Side}};
+//! let grid = DynamicGrid::new_from_iter(3, "....#.###".chars()).unwrap();
+//! let shape = |c: &char| if *c == '#' { CollisionShape::Solid } else { CollisionShape::Empty };
+//! let player = Aabb::new([0.25, 0.25], [0.75, 0.75]);
+//! let result = grid.move_aabb(player, [0.0, 2.0], [1.0, 1.0], shape);
+//! assert_eq!(result.aabb, Aabb::new([0.25, 1.5], [0.75, 2.0]));
+//! assert_eq!(result.velocity, [0.0, 0.0]);
+//! assert_eq!(result.contacts[0].side, Side::Top);
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{
+    numeric::{ceil, floor, i64_as_f32, to_i64},
+    DynamicGrid,
+};
+
+/// An axis-aligned box in world space
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Aabb {
+    /// Top-left corner
+    pub min: [f32; 2],
+    /// Bottom-right corner
+    pub max: [f32; 2],
+}
+
+/// Collision shape of a cell
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum CollisionShape {
+    /// The cell does not collide
+    Empty,
+    /// The cell collides on all its sides
+    Solid,
+    /// One-way platform: the cell only collides with boxes moving down onto its top side
+    Platform,
+}
+
+/// Side of a cell
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Side {
+    /// The side facing negative x
+    Left,
+    /// The side facing positive x
+    Right,
+    /// The side facing negative y
+    Top,
+    /// The side facing positive y
+    Bottom,
+}
+
+/// A cell hit during a move
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Contact {
+    /// Coordinate (col, row) of the hit cell
+    pub cell: (usize, usize),
+    /// Side of the cell that was hit
+    pub side: Side,
+    /// Fraction (from `0` to `1`) of the move along the axis at which the cell was hit
+    pub time_of_impact: f32,
+}
+
+/// Result of [`DynamicGrid::move_aabb`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct MoveResult {
+    /// The box after the move
+    pub aabb: Aabb,
+    /// The velocity, where the components along which the move was blocked are set to zero
+    pub velocity: [f32; 2],
+    /// The cells that were hit
+    pub contacts: Vec<Contact>,
+}
+
+impl Aabb {
+    /// Create a box from its top-left (`min`) and bottom-right (`max`) corners
+    #[must_use]
+    pub fn new(min: [f32; 2], max: [f32; 2]) -> Self {
+        Self { min, max }
+    }
+}
+
+impl<T> DynamicGrid<T> {
+    /// Move the `aabb` by `velocity`, stopping at the cells it collides with
+    ///
+    /// The move is resolved along the x axis first, then along the y axis.
+    /// The `shape` function returns the collision shape of a cell. Cells out of bounds never collide.
+    ///
+    /// The box is not moved if a coordinate of `aabb` is not finite,
+    /// or if a component of `cell_size` is not finite or not strictly positive.
+    #[must_use]
+    pub fn move_aabb(
+        &self,
+        aabb: Aabb,
+        velocity: [f32; 2],
+        cell_size: [f32; 2],
+        mut shape: impl FnMut(&T) -> CollisionShape,
+    ) -> MoveResult {
+        let mut result = MoveResult {
+            aabb,
+            velocity,
+            contacts: Vec::new(),
+        };
+        let invalid_aabb = aabb.min.iter().chain(&aabb.max).any(|v| !v.is_finite());
+        if invalid_aabb || cell_size.iter().any(|s| !s.is_finite() || *s <= 0.0) {
+            return result;
+        }
+        for axis in 0..2 {
+            self.move_along(&mut result, axis, cell_size, &mut shape);
+        }
+        result
+    }
+
+    fn move_along(
+        &self,
+        result: &mut MoveResult,
+        axis: usize,
+        cell_size: [f32; 2],
+        shape: &mut impl FnMut(&T) -> CollisionShape,
+    ) {
+        let other = 1 - axis;
+        let velocity = result.velocity[axis];
+        if velocity == 0.0 || !velocity.is_finite() {
+            return;
+        }
+        let Aabb { min, max } = result.aabb;
+        let length = cell_size[axis];
+        let positive = velocity > 0.0;
+        let limits = [to_i64(self.width), to_i64(self.height_or_zero())];
+        let spanned = floor(min[other] / cell_size[other]).max(0)
+            ..ceil(max[other] / cell_size[other]).min(limits[other]);
+        let clamp = |from: i64, to: i64| from.max(0)..to.min(limits[axis]);
+        let (candidates, hit_side): (Vec<i64>, Side) = if positive {
+            let range = clamp(
+                ceil(max[axis] / length),
+                ceil((max[axis] + velocity) / length),
+            );
+            (range.collect(), [Side::Left, Side::Top][axis])
+        } else {
+            let range = clamp(
+                floor((min[axis] + velocity) / length),
+                floor(min[axis] / length),
+            );
+            (range.rev().collect(), [Side::Right, Side::Bottom][axis])
+        };
+        for line in candidates {
+            let mut contacts = spanned.clone().filter_map(|lane| {
+                let (x, y) = if axis == 0 {
+                    (line, lane)
+                } else {
+                    (lane, line)
+                };
+                let index = self.cell_index((x, y))?;
+                let collides = match shape(&self.cells[index]) {
+                    CollisionShape::Solid => true,
+                    CollisionShape::Platform => axis == 1 && positive,
+                    CollisionShape::Empty => false,
+                };
+                collides.then(|| Self::index_to_coord(index, self.width))
+            });
+            let first = match contacts.next() {
+                Some(cell) => cell,
+                None => continue,
+            };
+            let face = i64_as_f32(if positive { line } else { line + 1 }) * length;
+            let allowed = if positive {
+                face - max[axis]
+            } else {
+                face - min[axis]
+            };
+            let time_of_impact = allowed / velocity;
+            let contacts: Vec<Contact> = core::iter::once(first)
+                .chain(contacts)
+                .map(|cell| Contact {
+                    cell,
+                    side: hit_side,
+                    time_of_impact,
+                })
+                .collect();
+            result.contacts.extend(contacts);
+            result.aabb.min[axis] += allowed;
+            result.aabb.max[axis] += allowed;
+            result.velocity[axis] = 0.0;
+            return;
+        }
+        result.aabb.min[axis] += velocity;
+        result.aabb.max[axis] += velocity;
+    }
+}
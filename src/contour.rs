@@ -0,0 +1,311 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec,
+    vec::Vec,
+};
+
+use crate::{numeric::as_f32, DynamicGrid};
+
+/// A polyline in cell space, returned by [`DynamicGrid::contours`] and [`DynamicGrid::outlines`]
+///
+/// In cell space, the cell at col `x` and row `y` covers the area from `[x, y]` to `[x + 1, y + 1]`.
+///
+/// Contours are oriented such that the region inside (above the level, or matching the predicate)
+/// is on the right side when following the points, with x pointing right and y pointing down.
+/// Hence, outer boundaries are clockwise and holes are counter-clockwise.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Contour {
+    /// Points of the polyline
+    pub points: Vec<[f32; 2]>,
+    /// Whether the last point is connected to the first one
+    pub closed: bool,
+}
+
+type EdgeKey = (bool, usize, usize);
+
+impl<T> DynamicGrid<T> {
+    /// Extract the iso-lines where the value returned by `iso` crosses `level`, using marching squares
+    ///
+    /// Values are sampled at the center of the cells, and interpolated linearly between them.
+    /// Cells for which `iso` returns NaN are considered below the level.
+    ///
+    /// Contours are closed, unless they reach the edge of the grid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_from_iter(3, [0, 0, 0, 0, 1, 0, 0, 0, 0]).unwrap();
+    /// let contours = grid.contours(|c| *c as f32, 0.5);
+    /// assert_eq!(contours.len(), 1);
+    /// assert!(contours[0].closed);
+    /// assert_eq!(contours[0].points, [[1.0, 1.5], [1.5, 1.0], [2.0, 1.5], [1.5, 2.0]]);
+    /// ```
+    #[must_use]
+    pub fn contours(&self, mut iso: impl FnMut(&T) -> f32, level: f32) -> Vec<Contour> {
+        let width = self.width;
+        let height = self.height_or_zero();
+        let values: Vec<f32> = self.cells.iter().map(&mut iso).collect();
+        let value = |(x, y): (usize, usize)| values[y * width + x];
+        let inside = |corner: (usize, usize)| value(corner) >= level;
+        let mut segments = Vec::new();
+        for y in 0..height.saturating_sub(1) {
+            for x in 0..width.saturating_sub(1) {
+                let corners = [(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)];
+                let keys: [EdgeKey; 4] = [
+                    (false, x, y),
+                    (true, x + 1, y),
+                    (false, x, y + 1),
+                    (true, x, y),
+                ];
+                let crossings: Vec<(EdgeKey, bool)> = (0..4)
+                    .filter_map(|i| {
+                        let (from, to) = (inside(corners[i]), inside(corners[(i + 1) % 4]));
+                        (from != to).then(|| (keys[i], from))
+                    })
+                    .collect();
+                let connected = crossings.len() == 4 && {
+                    let sum: f32 = corners.iter().map(|c| value(*c)).sum();
+                    sum / 4.0 >= level
+                };
+                let count = crossings.len();
+                for (i, (key, exit)) in crossings.iter().enumerate() {
+                    if *exit {
+                        let enter = if connected { i + 1 } else { i + count - 1 };
+                        segments.push((*key, crossings[enter % count].0));
+                    }
+                }
+            }
+        }
+        let point = |(vertical, x, y): EdgeKey| {
+            let other = if vertical { (x, y + 1) } else { (x + 1, y) };
+            let (from, to) = (value((x, y)), value(other));
+            let t = (level - from) / (to - from);
+            let t = if t.is_finite() {
+                t.clamp(0.0, 1.0)
+            } else {
+                0.5
+            };
+            let (x, y) = (as_f32(x) + 0.5, as_f32(y) + 0.5);
+            if vertical {
+                [x, y + t]
+            } else {
+                [x + t, y]
+            }
+        };
+        link(&segments, |_, _, _| 0)
+            .into_iter()
+            .map(|(keys, closed)| Contour {
+                points: keys.into_iter().map(point).collect(),
+                closed,
+            })
+            .collect()
+    }
+
+    /// Extract the outlines of the regions of cells matching the `predicate`, following the edges of the cells
+    ///
+    /// All outlines are closed, and only contain the corners of the regions.
+    /// Regions touching only by a corner get separate outlines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_from_iter(3, "..####...".chars()).unwrap();
+    /// let outlines = grid.outlines(|c| *c == '#');
+    /// assert_eq!(outlines.len(), 1);
+    /// assert_eq!(
+    ///     outlines[0].points,
+    ///     [[2.0, 0.0], [3.0, 0.0], [3.0, 2.0], [0.0, 2.0], [0.0, 1.0], [2.0, 1.0]],
+    /// );
+    /// ```
+    #[must_use]
+    pub fn outlines(&self, mut predicate: impl FnMut(&T) -> bool) -> Vec<Contour> {
+        let width = self.width;
+        let height = self.height_or_zero();
+        let inside: Vec<bool> = self.cells.iter().map(&mut predicate).collect();
+        let is_inside = |x: Option<usize>, y: Option<usize>| match (x, y) {
+            (Some(x), Some(y)) if x < width && y < height => inside[y * width + x],
+            _ => false,
+        };
+        let mut segments = Vec::new();
+        for (index, _) in inside.iter().enumerate().filter(|(_, i)| **i) {
+            let (x, y) = Self::index_to_coord(index, width);
+            let sides = [
+                ((x, y), (x + 1, y), is_inside(Some(x), y.checked_sub(1))),
+                ((x + 1, y), (x + 1, y + 1), is_inside(Some(x + 1), Some(y))),
+                ((x + 1, y + 1), (x, y + 1), is_inside(Some(x), Some(y + 1))),
+                ((x, y + 1), (x, y), is_inside(x.checked_sub(1), Some(y))),
+            ];
+            for (from, to, _) in sides.into_iter().filter(|(_, _, covered)| !covered) {
+                segments.push((from, to));
+            }
+        }
+        // Prefer turning right, so that regions touching by a corner are not merged
+        let turn = |from: (usize, usize), corner: (usize, usize), to: (usize, usize)| {
+            let incoming = direction(from, corner);
+            let outgoing = direction(corner, to);
+            incoming.0 * outgoing.1 - incoming.1 * outgoing.0
+        };
+        link(&segments, turn)
+            .into_iter()
+            .map(|(corners, closed)| {
+                let count = corners.len();
+                let points = (0..count)
+                    .filter(|i| {
+                        let previous = corners[(i + count - 1) % count];
+                        let next = corners[(i + 1) % count];
+                        direction(previous, corners[*i]) != direction(corners[*i], next)
+                    })
+                    .map(|i| [as_f32(corners[i].0), as_f32(corners[i].1)])
+                    .collect();
+                Contour { points, closed }
+            })
+            .collect()
+    }
+}
+
+impl Contour {
+    /// Simplify the contour with the Ramer–Douglas–Peucker algorithm
+    ///
+    /// Points closer than `epsilon` to the simplified polyline are removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_with(5, 5, |x, y| x + y < 5);
+    /// let outline = &grid.outlines(|c| *c)[0];
+    /// assert_eq!(outline.points.len(), 12);
+    /// assert_eq!(outline.simplify(1.0).points.len(), 3);
+    /// ```
+    #[must_use]
+    pub fn simplify(&self, epsilon: f32) -> Contour {
+        let mut points = self.points.clone();
+        if self.closed {
+            points.extend(self.points.first().copied());
+        }
+        let mut keep = vec![false; points.len()];
+        if let (Some(first), Some(last)) = (keep.first_mut(), points.len().checked_sub(1)) {
+            *first = true;
+            keep[last] = true;
+            simplify_range(&points, 0, last, epsilon * epsilon, &mut keep);
+        }
+        let mut simplified: Vec<[f32; 2]> = points
+            .into_iter()
+            .zip(keep)
+            .filter(|(_, keep)| *keep)
+            .map(|(point, _)| point)
+            .collect();
+        if self.closed && simplified.len() > 1 {
+            simplified.pop();
+        }
+        Contour {
+            points: simplified,
+            closed: self.closed,
+        }
+    }
+}
+
+fn simplify_range(
+    points: &[[f32; 2]],
+    start: usize,
+    end: usize,
+    squared_epsilon: f32,
+    keep: &mut [bool],
+) {
+    if end <= start + 1 {
+        return;
+    }
+    let (a, b) = (points[start], points[end]);
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let squared_length = dx * dx + dy * dy;
+    let squared_distance = |p: [f32; 2]| {
+        let (px, py) = (p[0] - a[0], p[1] - a[1]);
+        if squared_length == 0.0 {
+            px * px + py * py
+        } else {
+            let cross = px * dy - py * dx;
+            cross * cross / squared_length
+        }
+    };
+    let farthest = (start + 1..end)
+        .map(|i| (i, squared_distance(points[i])))
+        .fold((start, 0.0), |best, current| {
+            if current.1 > best.1 {
+                current
+            } else {
+                best
+            }
+        });
+    if farthest.1 > squared_epsilon {
+        keep[farthest.0] = true;
+        simplify_range(points, start, farthest.0, squared_epsilon, keep);
+        simplify_range(points, farthest.0, end, squared_epsilon, keep);
+    }
+}
+
+/// Link the oriented `segments` into polylines
+///
+/// When several segments start from the same point, the one with the highest `preference`
+/// (given the previous point, the current point and the next point) is taken.
+fn link<K: Copy + Ord>(
+    segments: &[(K, K)],
+    mut preference: impl FnMut(K, K, K) -> i64,
+) -> Vec<(Vec<K>, bool)> {
+    let mut outgoing: BTreeMap<K, Vec<usize>> = BTreeMap::new();
+    let mut has_incoming: BTreeSet<K> = BTreeSet::new();
+    for (index, (from, to)) in segments.iter().enumerate() {
+        outgoing.entry(*from).or_default().push(index);
+        has_incoming.insert(*to);
+    }
+    let mut used = vec![false; segments.len()];
+    let open_starts = (0..segments.len()).filter(|i| !has_incoming.contains(&segments[*i].0));
+    let starts: Vec<(usize, bool)> = open_starts
+        .map(|i| (i, false))
+        .chain((0..segments.len()).map(|i| (i, true)))
+        .collect();
+    let mut polylines = Vec::new();
+    for (first, closed) in starts {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let (start, mut previous) = segments[first];
+        let mut keys = vec![start];
+        let mut current = previous;
+        previous = start;
+        loop {
+            let next = outgoing.get(&current).and_then(|candidates| {
+                candidates
+                    .iter()
+                    .copied()
+                    .filter(|i| !used[*i])
+                    .max_by_key(|i| preference(previous, current, segments[*i].1))
+            });
+            let next = match next {
+                Some(next) => next,
+                None => break,
+            };
+            keys.push(current);
+            used[next] = true;
+            previous = current;
+            current = segments[next].1;
+        }
+        if !closed {
+            keys.push(current);
+        }
+        polylines.push((keys, closed));
+    }
+    polylines
+}
+
+fn direction(from: (usize, usize), to: (usize, usize)) -> (i64, i64) {
+    let delta = |a: usize, b: usize| match a.cmp(&b) {
+        core::cmp::Ordering::Less => 1,
+        core::cmp::Ordering::Equal => 0,
+        core::cmp::Ordering::Greater => -1,
+    };
+    (delta(from.0, to.0), delta(from.1, to.1))
+}
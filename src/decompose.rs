@@ -0,0 +1,165 @@
+use alloc::{vec, vec::Vec};
+
+use crate::DynamicGrid;
+
+/// A rectangle of cells, starting at col `x` and row `y`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct CellRect {
+    /// Col of the top-left cell
+    pub x: usize,
+    /// Row of the top-left cell
+    pub y: usize,
+    /// Number of cols
+    pub width: usize,
+    /// Number of rows
+    pub height: usize,
+}
+
+/// Strategy of [`DynamicGrid::decompose_into_rects`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum DecompositionMode {
+    /// Scan the cells in row-major order, and extend each uncovered cell as far right, then as far down as possible
+    ///
+    /// It is fast (linear in the number of cells), but may produce more rectangles than necessary.
+    Greedy,
+    /// Repeatedly take the largest rectangle of uncovered cells
+    ///
+    /// It is slower (linear in the number of cells for each rectangle), but usually produces fewer rectangles.
+    LargestFirst,
+}
+
+impl CellRect {
+    /// Create a rectangle from its top-left cell, and its size
+    #[must_use]
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the number of cells in the rectangle
+    #[must_use]
+    pub fn area(&self) -> usize {
+        self.width * self.height
+    }
+}
+
+impl<T> DynamicGrid<T> {
+    /// Returns non-overlapping rectangles that exactly cover the cells matching the `predicate`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{CellRect, DecompositionMode, DynamicGrid};
+    /// let grid = DynamicGrid::new_from_iter(3, "##.###.##".chars()).unwrap();
+    /// let rects = grid.decompose_into_rects(DecompositionMode::Greedy, |c| *c == '#');
+    /// assert_eq!(
+    ///     rects,
+    ///     [CellRect::new(0, 0, 2, 2), CellRect::new(2, 1, 1, 2), CellRect::new(1, 2, 1, 1)],
+    /// );
+    /// ```
+    #[must_use]
+    pub fn decompose_into_rects(
+        &self,
+        mode: DecompositionMode,
+        predicate: impl FnMut(&T) -> bool,
+    ) -> Vec<CellRect> {
+        let mut uncovered: Vec<bool> = self.cells.iter().map(predicate).collect();
+        let width = self.width;
+        let height = self.height_or_zero();
+        let mut rects = Vec::new();
+        match mode {
+            DecompositionMode::Greedy => {
+                for index in 0..uncovered.len() {
+                    if !uncovered[index] {
+                        continue;
+                    }
+                    let (x, y) = Self::index_to_coord(index, width);
+                    let run = uncovered[index..(y + 1) * width]
+                        .iter()
+                        .take_while(|c| **c)
+                        .count();
+                    let rows = 1
+                        + (y + 1..height)
+                            .take_while(|row| {
+                                let start = row * width + x;
+                                uncovered[start..start + run].iter().all(|c| *c)
+                            })
+                            .count();
+                    let rect = CellRect::new(x, y, run, rows);
+                    cover(&mut uncovered, width, rect);
+                    rects.push(rect);
+                }
+            }
+            DecompositionMode::LargestFirst => {
+                while let Some(rect) = largest_rect(&uncovered, width, height) {
+                    cover(&mut uncovered, width, rect);
+                    rects.push(rect);
+                }
+            }
+        }
+        rects
+    }
+
+    /// Returns the largest rectangle in which all the cells are empty (according to `is_empty`)
+    ///
+    /// Returns `None` if there are no empty cells.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{CellRect, DynamicGrid};
+    /// let grid = DynamicGrid::new_from_iter(4, "#..##...#..##..#".chars()).unwrap();
+    /// let rect = grid.largest_empty_rect(|c| *c == '.');
+    /// assert_eq!(rect, Some(CellRect::new(1, 0, 2, 4)));
+    /// ```
+    #[must_use]
+    pub fn largest_empty_rect(&self, is_empty: impl FnMut(&T) -> bool) -> Option<CellRect> {
+        let empty: Vec<bool> = self.cells.iter().map(is_empty).collect();
+        largest_rect(&empty, self.width, self.height_or_zero())
+    }
+}
+
+fn cover(cells: &mut [bool], width: usize, rect: CellRect) {
+    for y in rect.y..rect.y + rect.height {
+        let start = y * width + rect.x;
+        for cell in &mut cells[start..start + rect.width] {
+            *cell = false;
+        }
+    }
+}
+
+/// Find the largest rectangle of `true` cells, by computing the largest rectangle in the histogram of each row
+fn largest_rect(cells: &[bool], width: usize, height: usize) -> Option<CellRect> {
+    let mut heights = vec![0; width];
+    let mut stack: Vec<(usize, usize)> = Vec::with_capacity(width);
+    let mut best: Option<CellRect> = None;
+    for y in 0..height {
+        for (x, h) in heights.iter_mut().enumerate() {
+            *h = if cells[y * width + x] { *h + 1 } else { 0 };
+        }
+        stack.clear();
+        for x in 0..=width {
+            let h = heights.get(x).copied().unwrap_or(0);
+            let mut start = x;
+            while let Some(&(left, top)) = stack.last() {
+                if top < h {
+                    break;
+                }
+                stack.pop();
+                let rect = CellRect::new(left, y + 1 - top, x - left, top);
+                if top > 0 && best.map_or(true, |best| rect.area() > best.area()) {
+                    best = Some(rect);
+                }
+                start = left;
+            }
+            stack.push((start, h));
+        }
+    }
+    best
+}
@@ -0,0 +1,258 @@
+use core::{fmt::Display, str::FromStr};
+use std::io;
+
+use crate::DynamicGrid;
+
+/// Options of [`DynamicGrid::read_csv`] and [`DynamicGrid::write_csv`]
+///
+/// # Example
+///
+/// ```
+/// # use cell_grid::CsvOptions;
+/// let tsv = CsvOptions::default().delimiter(b'\t').with_header();
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CsvOptions {
+    delimiter: u8,
+    quote: u8,
+    always_quote: bool,
+    header: bool,
+}
+
+/// Error returned when reading or writing CSV
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CsvError {
+    row: Option<usize>,
+    column: Option<usize>,
+    kind: CsvErrorKind,
+}
+
+#[derive(Debug)]
+enum CsvErrorKind {
+    Csv(csv::Error),
+    Parse(String),
+    IncompatibleRowSize { expected: usize, found: usize },
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            always_quote: false,
+            header: false,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Set the field delimiter (`,` by default)
+    #[must_use]
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the quote character (`"` by default)
+    #[must_use]
+    pub fn quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Quote all the fields when writing (by default, only the fields that need it are quoted)
+    #[must_use]
+    pub fn always_quote(mut self) -> Self {
+        self.always_quote = true;
+        self
+    }
+
+    /// The first row is a header
+    ///
+    /// When reading, the first row is skipped. When writing, a first row with the column numbers (starting at `0`) is written.
+    #[must_use]
+    pub fn with_header(mut self) -> Self {
+        self.header = true;
+        self
+    }
+}
+
+impl<T> DynamicGrid<T> {
+    /// Read a grid from CSV, parsing each field with [`FromStr`]
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CsvError`] if the CSV is invalid, if a field cannot be parsed,
+    /// or if the rows do not all have the same number of fields.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{CsvOptions, DynamicGrid};
+    /// let csv = "1,2,3\n4,5,6\n";
+    /// let grid: DynamicGrid<i32> = DynamicGrid::read_csv(csv.as_bytes(), &CsvOptions::default()).unwrap();
+    /// assert_eq!(grid.get(2, 1), Some(&6));
+    ///
+    /// let error = DynamicGrid::<i32>::read_csv("1,2\n3,x\n".as_bytes(), &CsvOptions::default()).unwrap_err();
+    /// assert_eq!((error.row(), error.column()), (Some(2), Some(2)));
+    /// ```
+    pub fn read_csv(reader: impl io::Read, options: &CsvOptions) -> Result<Self, CsvError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        Self::read_csv_with(reader, options, str::parse)
+    }
+
+    /// Read a grid from CSV, converting each field with `converter`
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CsvError`] if the CSV is invalid, if the `converter` returns an error,
+    /// or if the rows do not all have the same number of fields.
+    pub fn read_csv_with<E: Display>(
+        reader: impl io::Read,
+        options: &CsvOptions,
+        mut converter: impl FnMut(&str) -> Result<T, E>,
+    ) -> Result<Self, CsvError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .delimiter(options.delimiter)
+            .quote(options.quote)
+            .from_reader(reader);
+        let mut grid = Self::new();
+        let skip = usize::from(options.header);
+        for (index, record) in reader.records().enumerate().skip(skip) {
+            let row = index + 1;
+            let record =
+                record.map_err(|error| CsvError::new(Some(row), None, CsvErrorKind::Csv(error)))?;
+            if !grid.is_empty() && record.len() != grid.width {
+                return Err(CsvError::new(
+                    Some(row),
+                    None,
+                    CsvErrorKind::IncompatibleRowSize {
+                        expected: grid.width,
+                        found: record.len(),
+                    },
+                ));
+            }
+            let cells = record.iter().enumerate().map(|(column, field)| {
+                converter(field).map_err(|error| {
+                    CsvError::new(
+                        Some(row),
+                        Some(column + 1),
+                        CsvErrorKind::Parse(error.to_string()),
+                    )
+                })
+            });
+            let cells = cells.collect::<Result<Vec<T>, CsvError>>()?;
+            if !cells.is_empty() {
+                grid.width = cells.len();
+                grid.cells.extend(cells);
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Write the grid as CSV, formatting each cell with [`Display`]
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CsvError`] if writing fails
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{CsvOptions, DynamicGrid};
+    /// let grid = DynamicGrid::new_from_iter(2, [1, 2, 3, 4]).unwrap();
+    /// let mut csv = Vec::new();
+    /// grid.write_csv(&mut csv, &CsvOptions::default().delimiter(b';').with_header()).unwrap();
+    /// assert_eq!(String::from_utf8(csv).unwrap(), "0;1\n1;2\n3;4\n");
+    /// ```
+    pub fn write_csv(&self, writer: impl io::Write, options: &CsvOptions) -> Result<(), CsvError>
+    where
+        T: Display,
+    {
+        self.write_csv_with(writer, options, ToString::to_string)
+    }
+
+    /// Write the grid as CSV, converting each cell to a field with `converter`
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CsvError`] if writing fails
+    pub fn write_csv_with(
+        &self,
+        writer: impl io::Write,
+        options: &CsvOptions,
+        mut converter: impl FnMut(&T) -> String,
+    ) -> Result<(), CsvError> {
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(options.delimiter)
+            .quote(options.quote)
+            .quote_style(if options.always_quote {
+                csv::QuoteStyle::Always
+            } else {
+                csv::QuoteStyle::Necessary
+            })
+            .from_writer(writer);
+        let to_error = |error| CsvError::new(None, None, CsvErrorKind::Csv(error));
+        if options.header {
+            writer
+                .write_record((0..self.width).map(|column| column.to_string()))
+                .map_err(to_error)?;
+        }
+        for row in self.cells.chunks(self.width.max(1)) {
+            writer
+                .write_record(row.iter().map(&mut converter))
+                .map_err(to_error)?;
+        }
+        writer.flush().map_err(|error| to_error(error.into()))
+    }
+}
+
+impl CsvError {
+    fn new(row: Option<usize>, column: Option<usize>, kind: CsvErrorKind) -> Self {
+        Self { row, column, kind }
+    }
+
+    /// Returns the row (starting at `1`, and including the header) where the error occurred, if it is known
+    #[must_use]
+    pub fn row(&self) -> Option<usize> {
+        self.row
+    }
+
+    /// Returns the column (starting at `1`) where the error occurred, if it is known
+    #[must_use]
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+}
+
+impl Display for CsvError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match (self.row, self.column) {
+            (Some(row), Some(column)) => write!(f, "At row {row}, column {column}: ")?,
+            (Some(row), None) => write!(f, "At row {row}: ")?,
+            _ => {}
+        }
+        match &self.kind {
+            CsvErrorKind::Csv(error) => write!(f, "{error}"),
+            CsvErrorKind::Parse(error) => write!(f, "Invalid field: {error}"),
+            CsvErrorKind::IncompatibleRowSize { expected, found } => {
+                write!(f, "Expected {expected} fields, found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CsvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            CsvErrorKind::Csv(error) => Some(error),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use core::cmp::Reverse;
+
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+
+use crate::{DynamicGrid, Neighborhood};
+
+/// Cost of moving into a cell, and accumulated distance in a dijkstra map
+///
+/// Implemented for all primitive integer types.
+///
+/// Distances are accumulated with [`Cost::checked_add`]: a cell whose distance would overflow is treated as unreachable.
+pub trait Cost: Copy + Ord {
+    /// The cost of not moving at all
+    const ZERO: Self;
+
+    /// Returns `self + other`, or `None` if the result overflows
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_cost {
+    ($($t:ty),*) => {
+        $(impl Cost for $t {
+            const ZERO: Self = 0;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+        })*
+    };
+}
+
+impl_cost!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl<T> DynamicGrid<T> {
+    /// Compute the distance from every cell to the nearest of the `goals`
+    ///
+    /// The `cost` function returns the cost of moving into a cell, or `None` if the cell cannot be entered.
+    /// Cells that cannot reach any goal are `None` in the resulting grid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, Neighborhood};
+    /// let grid = DynamicGrid::new_from_iter(3, ['.', '#', '.', '.', '.', '.']).unwrap();
+    /// let map = grid.dijkstra_map([(0, 0)], Neighborhood::VonNeumann, |c| (*c == '.').then(|| 1));
+    /// assert_eq!(map.get(0, 0), Some(&Some(0)));
+    /// assert_eq!(map.get(1, 0), Some(&None));
+    /// assert_eq!(map.get(2, 0), Some(&Some(4)));
+    /// ```
+    #[must_use]
+    pub fn dijkstra_map<C: Cost>(
+        &self,
+        goals: impl IntoIterator<Item = (usize, usize)>,
+        neighborhood: Neighborhood,
+        cost: impl FnMut(&T) -> Option<C>,
+    ) -> DynamicGrid<Option<C>> {
+        self.dijkstra_map_from_seeds(
+            goals.into_iter().map(|coord| (coord, C::ZERO)),
+            neighborhood,
+            cost,
+        )
+    }
+
+    /// Compute a dijkstra map from `seeds` having each an initial value
+    ///
+    /// This is a generalization of [`Self::dijkstra_map`] where each goal may start at a different value.
+    /// Seed values may be negative, as long as the `cost` of entering cells is not.
+    /// Seeds that are out of bounds are ignored.
+    #[must_use]
+    pub fn dijkstra_map_from_seeds<C: Cost>(
+        &self,
+        seeds: impl IntoIterator<Item = ((usize, usize), C)>,
+        neighborhood: Neighborhood,
+        cost: impl FnMut(&T) -> Option<C>,
+    ) -> DynamicGrid<Option<C>> {
+        let mut map = DynamicGrid {
+            cells: vec![None; self.cells.len()],
+            width: self.width,
+        };
+        let costs: Vec<Option<C>> = self.cells.iter().map(cost).collect();
+        let mut heap = BinaryHeap::new();
+        for (index, value) in map.seed_values(seeds).into_iter().enumerate() {
+            if let Some(value) = value {
+                map.cells[index] = Some(value);
+                heap.push(Reverse((value, index)));
+            }
+        }
+        map.propagate(&costs, heap, neighborhood);
+        map
+    }
+
+    /// Compute a "flee map" from a dijkstra map computed `toward` some goals
+    ///
+    /// Every reachable cell of `toward` is used as a seed, after being transformed by `scale`.
+    /// Scaling the distances by a negative factor (typically around `-1.2`) produces a map leading away from the goals,
+    /// while still preferring escape routes over dead ends.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, Neighborhood};
+    /// let grid = DynamicGrid::new_with(5, 1, |_, _| ());
+    /// let toward = grid.dijkstra_map([(0, 0)], Neighborhood::Moore, |()| Some(10_i32));
+    /// let flee = grid.flee_map(&toward, |d| d * -12 / 10, Neighborhood::Moore, |()| Some(10));
+    /// let best = flee.cells_with_coords().min_by_key(|(_, d)| *d).unwrap();
+    /// assert_eq!(best.0, (4, 0));
+    /// ```
+    #[must_use]
+    pub fn flee_map<C: Cost>(
+        &self,
+        toward: &DynamicGrid<Option<C>>,
+        mut scale: impl FnMut(C) -> C,
+        neighborhood: Neighborhood,
+        cost: impl FnMut(&T) -> Option<C>,
+    ) -> DynamicGrid<Option<C>> {
+        self.dijkstra_map_from_seeds(
+            toward
+                .cells_with_coords()
+                .filter_map(|(coord, distance)| Some((coord, scale((*distance)?)))),
+            neighborhood,
+            cost,
+        )
+    }
+
+    /// Update a dijkstra `map` after the cells in the given rectangle have changed
+    ///
+    /// The `seeds`, `neighborhood` and `cost` must be the same as the ones used to create the `map`.
+    /// Only the cells whose distance depended on the changed rectangle are recomputed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of `map` does not match the size of this grid
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_dijkstra_map<C: Cost>(
+        &self,
+        map: &mut DynamicGrid<Option<C>>,
+        seeds: impl IntoIterator<Item = ((usize, usize), C)>,
+        neighborhood: Neighborhood,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        cost: impl FnMut(&T) -> Option<C>,
+    ) {
+        assert!(
+            map.width == self.width && map.cells.len() == self.cells.len(),
+            "the dijkstra map size does not match the grid size"
+        );
+        if self.cells.is_empty() {
+            return;
+        }
+        let costs: Vec<Option<C>> = self.cells.iter().map(cost).collect();
+        let seeds = map.seed_values(seeds);
+        let (grid_width, grid_height) = (map.width, map.height());
+        let mut invalid = vec![false; map.cells.len()];
+        let mut stack: Vec<usize> = (y..y.saturating_add(height).min(grid_height))
+            .flat_map(|y| {
+                (x..x.saturating_add(width).min(grid_width)).map(move |x| y * grid_width + x)
+            })
+            .collect();
+        for &index in &stack {
+            invalid[index] = true;
+            map.cells[index] = None;
+        }
+        while let Some(index) = stack.pop() {
+            let (x, y) = Self::index_to_coord(index, map.width);
+            for (nx, ny) in neighborhood.neighbors(x, y, map.width, grid_height) {
+                let neighbor = ny * map.width + nx;
+                if !invalid[neighbor]
+                    && map.cells[neighbor].is_some()
+                    && !map.is_supported(neighbor, &costs, &seeds, neighborhood)
+                {
+                    invalid[neighbor] = true;
+                    map.cells[neighbor] = None;
+                    stack.push(neighbor);
+                }
+            }
+        }
+        let mut heap = BinaryHeap::new();
+        for (index, _) in invalid.iter().enumerate().filter(|(_, i)| **i) {
+            if let Some(value) = seeds[index] {
+                map.cells[index] = Some(value);
+                heap.push(Reverse((value, index)));
+            }
+            let (x, y) = Self::index_to_coord(index, map.width);
+            for (nx, ny) in neighborhood.neighbors(x, y, map.width, grid_height) {
+                let neighbor = ny * map.width + nx;
+                if let (false, Some(value)) = (invalid[neighbor], map.cells[neighbor]) {
+                    heap.push(Reverse((value, neighbor)));
+                }
+            }
+        }
+        map.propagate(&costs, heap, neighborhood);
+    }
+}
+
+impl<C: Cost> DynamicGrid<Option<C>> {
+    /// Derive a flow field from this dijkstra map
+    ///
+    /// Each cell of the resulting grid contains the coordinate of the neighbor with the lowest distance,
+    /// or `None` if there is no neighbor closer to a goal (goals, unreachable cells and local minimums).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, Neighborhood};
+    /// let grid = DynamicGrid::new_with(3, 3, |_, _| ());
+    /// let map = grid.dijkstra_map([(0, 0)], Neighborhood::Moore, |()| Some(1));
+    /// let flow = map.flow_field(Neighborhood::Moore);
+    /// assert_eq!(flow.get(2, 2), Some(&Some((1, 1))));
+    /// assert_eq!(flow.get(0, 0), Some(&None));
+    /// ```
+    #[must_use]
+    pub fn flow_field(&self, neighborhood: Neighborhood) -> DynamicGrid<Option<(usize, usize)>> {
+        if self.cells.is_empty() {
+            return DynamicGrid::new();
+        }
+        let height = self.height();
+        DynamicGrid::new_with(self.width, height, |x, y| {
+            let mut best = (self.cells[y * self.width + x]?, None);
+            for (nx, ny) in neighborhood.neighbors(x, y, self.width, height) {
+                match self.cells[ny * self.width + nx] {
+                    Some(distance) if distance < best.0 => best = (distance, Some((nx, ny))),
+                    _ => (),
+                }
+            }
+            best.1
+        })
+    }
+
+    fn seed_values(&self, seeds: impl IntoIterator<Item = ((usize, usize), C)>) -> Vec<Option<C>> {
+        let mut values = vec![None; self.cells.len()];
+        for ((x, y), value) in seeds {
+            if let Some(slot) = self.index(x, y).and_then(|i| values.get_mut(i)) {
+                *slot = Some(slot.map_or(value, |v: C| v.min(value)));
+            }
+        }
+        values
+    }
+
+    fn is_supported(
+        &self,
+        index: usize,
+        costs: &[Option<C>],
+        seeds: &[Option<C>],
+        neighborhood: Neighborhood,
+    ) -> bool {
+        let distance = match self.cells[index] {
+            Some(distance) => distance,
+            None => return false,
+        };
+        if seeds[index] == Some(distance) {
+            return true;
+        }
+        let cost = match costs[index] {
+            Some(cost) => cost,
+            None => return false,
+        };
+        let (x, y) = Self::index_to_coord(index, self.width);
+        neighborhood
+            .neighbors(x, y, self.width, self.height())
+            .filter_map(|(nx, ny)| self.cells[ny * self.width + nx])
+            .any(|d| d < distance && d.checked_add(cost) == Some(distance))
+    }
+
+    fn propagate(
+        &mut self,
+        costs: &[Option<C>],
+        mut heap: BinaryHeap<Reverse<(C, usize)>>,
+        neighborhood: Neighborhood,
+    ) {
+        if self.cells.is_empty() {
+            return;
+        }
+        let height = self.height();
+        while let Some(Reverse((distance, index))) = heap.pop() {
+            if self.cells[index] != Some(distance) {
+                continue;
+            }
+            let (x, y) = Self::index_to_coord(index, self.width);
+            for (nx, ny) in neighborhood.neighbors(x, y, self.width, height) {
+                let neighbor = ny * self.width + nx;
+                let cost = match costs[neighbor] {
+                    Some(cost) => cost,
+                    None => continue,
+                };
+                let candidate = match distance.checked_add(cost) {
+                    Some(candidate) => candidate,
+                    None => continue,
+                };
+                if self.cells[neighbor].map_or(true, |d| candidate < d) {
+                    self.cells[neighbor] = Some(candidate);
+                    heap.push(Reverse((candidate, neighbor)));
+                }
+            }
+        }
+    }
+}
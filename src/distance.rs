@@ -0,0 +1,248 @@
+use alloc::vec::Vec;
+
+use crate::{
+    neighborhood::offset,
+    numeric::{as_f64, to_u64},
+    DynamicGrid,
+};
+
+impl DynamicGrid<bool> {
+    /// Returns, for each cell, the coordinate (col, row) of the nearest `true` cell in euclidean distance
+    ///
+    /// This is the Voronoi assignment of the cells to the `true` cells.
+    /// The cells are `None` if there are no `true` cells in the grid.
+    /// Ties are broken arbitrarily.
+    ///
+    /// It is computed exactly in linear time (Felzenszwalb and Huttenlocher).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_with(5, 1, |x, _| x == 0 || x == 4);
+    /// let nearest = grid.nearest_feature();
+    /// assert_eq!(nearest.get(1, 0), Some(&Some((0, 0))));
+    /// assert_eq!(nearest.get(3, 0), Some(&Some((4, 0))));
+    /// ```
+    #[must_use]
+    pub fn nearest_feature(&self) -> DynamicGrid<Option<(usize, usize)>> {
+        let width = self.width;
+        let height = self.height_or_zero();
+        let mut nearest_row: Vec<Option<usize>> = Vec::with_capacity(self.cells.len());
+        nearest_row.resize(self.cells.len(), None);
+        for x in 0..width {
+            let mut last = None;
+            for y in 0..height {
+                if self.cells[y * width + x] {
+                    last = Some(y);
+                }
+                nearest_row[y * width + x] = last;
+            }
+            let mut next = None;
+            for y in (0..height).rev() {
+                if self.cells[y * width + x] {
+                    next = Some(y);
+                }
+                let cell = &mut nearest_row[y * width + x];
+                if let Some(below) = next {
+                    if cell.map_or(true, |above| below - y < y - above) {
+                        *cell = Some(below);
+                    }
+                }
+            }
+        }
+        let mut sites: Vec<usize> = Vec::with_capacity(width);
+        let mut starts: Vec<f64> = Vec::with_capacity(width);
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for (y, row) in nearest_row.chunks(width.max(1)).take(height).enumerate() {
+            lower_envelope(row, y, &mut sites, &mut starts);
+            let mut k = 0;
+            for x in 0..width {
+                while starts.get(k + 1).map_or(false, |start| *start <= as_f64(x)) {
+                    k += 1;
+                }
+                cells.push(
+                    sites
+                        .get(k)
+                        .and_then(|site| row[*site].map(|nearest| (*site, nearest))),
+                );
+            }
+        }
+        DynamicGrid { cells, width }
+    }
+
+    /// Returns, for each cell, the squared euclidean distance to the nearest `true` cell
+    ///
+    /// The cells are [`u64::MAX`] if there are no `true` cells in the grid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_with(3, 3, |x, y| (x, y) == (0, 0));
+    /// let distances = grid.squared_euclidean_distance_transform();
+    /// assert_eq!(distances.get(2, 1), Some(&5));
+    /// ```
+    #[must_use]
+    pub fn squared_euclidean_distance_transform(&self) -> DynamicGrid<u64> {
+        let nearest = self.nearest_feature();
+        DynamicGrid::new_with(self.width, self.height_or_zero(), |x, y| {
+            match nearest.cells[y * self.width + x] {
+                Some((fx, fy)) => {
+                    let (dx, dy) = (to_u64(x.abs_diff(fx)), to_u64(y.abs_diff(fy)));
+                    dx * dx + dy * dy
+                }
+                None => u64::MAX,
+            }
+        })
+    }
+
+    /// Returns, for each cell, the euclidean distance (in cells) to the nearest `true` cell
+    ///
+    /// The cells are [`f32::INFINITY`] if there are no `true` cells in the grid.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn euclidean_distance_transform(&self) -> DynamicGrid<f32> {
+        let squared = self.squared_euclidean_distance_transform();
+        #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+        DynamicGrid::new_with(self.width, self.height_or_zero(), |x, y| {
+            match squared.cells[y * self.width + x] {
+                u64::MAX => f32::INFINITY,
+                distance => (distance as f64).sqrt() as f32,
+            }
+        })
+    }
+
+    /// Returns the signed distance field of the `true` cells
+    ///
+    /// Each `false` cell contains the euclidean distance to the nearest `true` cell,
+    /// and each `true` cell contains the opposite of the distance to the nearest `false` cell.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_with(5, 1, |x, _| x < 3);
+    /// let sdf = grid.signed_distance_field();
+    /// assert_eq!(sdf.cells().copied().collect::<Vec<_>>(), [-3.0, -2.0, -1.0, 1.0, 2.0]);
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn signed_distance_field(&self) -> DynamicGrid<f32> {
+        let outside = self.euclidean_distance_transform();
+        let inside = DynamicGrid {
+            cells: self.cells.iter().map(|c| !c).collect(),
+            width: self.width,
+        }
+        .euclidean_distance_transform();
+        DynamicGrid::new_with(self.width, self.height_or_zero(), |x, y| {
+            let index = y * self.width + x;
+            if self.cells[index] {
+                -inside.cells[index]
+            } else {
+                outside.cells[index]
+            }
+        })
+    }
+
+    /// Returns, for each cell, the manhattan distance to the nearest `true` cell
+    ///
+    /// The cells are [`u32::MAX`] if there are no `true` cells in the grid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_with(3, 3, |x, y| (x, y) == (0, 0));
+    /// let distances = grid.manhattan_distance_transform();
+    /// assert_eq!(distances.get(2, 1), Some(&3));
+    /// ```
+    #[must_use]
+    pub fn manhattan_distance_transform(&self) -> DynamicGrid<u32> {
+        self.chamfer(&[(-1, 0), (0, -1)])
+    }
+
+    /// Returns, for each cell, the chebyshev distance to the nearest `true` cell
+    ///
+    /// The cells are [`u32::MAX`] if there are no `true` cells in the grid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_with(3, 3, |x, y| (x, y) == (0, 0));
+    /// let distances = grid.chebyshev_distance_transform();
+    /// assert_eq!(distances.get(2, 1), Some(&2));
+    /// ```
+    #[must_use]
+    pub fn chebyshev_distance_transform(&self) -> DynamicGrid<u32> {
+        self.chamfer(&[(-1, 0), (-1, -1), (0, -1), (1, -1)])
+    }
+
+    /// Two-pass distance transform, where `offsets` are the neighbors preceding a cell in row-major order
+    fn chamfer(&self, offsets: &[(i8, i8)]) -> DynamicGrid<u32> {
+        let width = self.width;
+        let height = self.height_or_zero();
+        let mut distances = DynamicGrid {
+            cells: self
+                .cells
+                .iter()
+                .map(|c| if *c { 0 } else { u32::MAX })
+                .collect(),
+            width,
+        };
+        let mut relax = |x: usize, y: usize, sign: i8| {
+            for &(dx, dy) in offsets {
+                let neighbor = offset(x, dx * sign, width).zip(offset(y, dy * sign, height));
+                if let Some((nx, ny)) = neighbor {
+                    let candidate = distances.cells[ny * width + nx].saturating_add(1);
+                    let cell = &mut distances.cells[y * width + x];
+                    *cell = (*cell).min(candidate);
+                }
+            }
+        };
+        for y in 0..height {
+            for x in 0..width {
+                relax(x, y, 1);
+            }
+        }
+        for y in (0..height).rev() {
+            for x in (0..width).rev() {
+                relax(x, y, -1);
+            }
+        }
+        distances
+    }
+}
+
+/// Compute the lower envelope of the parabolas `(x - site)² + (y - nearest_row[site])²`
+///
+/// After the call, `sites[k]` is the nearest site for the cells from `starts[k]` to `starts[k + 1]`.
+fn lower_envelope(
+    nearest_row: &[Option<usize>],
+    y: usize,
+    sites: &mut Vec<usize>,
+    starts: &mut Vec<f64>,
+) {
+    sites.clear();
+    starts.clear();
+    let height = |site: usize| {
+        let dy = as_f64(nearest_row[site].map_or(0, |row| y.abs_diff(row)));
+        dy * dy + as_f64(site) * as_f64(site)
+    };
+    let intersection = |p: usize, q: usize| (height(q) - height(p)) / (2.0 * as_f64(q - p));
+    for q in (0..nearest_row.len()).filter(|q| nearest_row[*q].is_some()) {
+        while let (Some(p), Some(start)) = (sites.last(), starts.last()) {
+            if intersection(*p, q) > *start {
+                break;
+            }
+            sites.pop();
+            starts.pop();
+        }
+        let start = sites
+            .last()
+            .map_or(f64::NEG_INFINITY, |p| intersection(*p, q));
+        sites.push(q);
+        starts.push(start);
+    }
+}
@@ -0,0 +1,309 @@
+use core::ops::{AddAssign, Mul};
+
+use alloc::vec::Vec;
+
+use crate::{
+    numeric::{as_f32, to_isize},
+    DynamicGrid, Neighborhood,
+};
+
+/// How cells out of bounds are sampled by filters
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum EdgeMode {
+    /// Out-of-bounds cells are ignored (which is equivalent to zero for convolutions)
+    Ignore,
+    /// Out-of-bounds cells take the value of the nearest cell on the edge
+    Clamp,
+    /// The grid wraps around
+    Wrap,
+    /// The grid is mirrored at the edges (the edge cell is repeated)
+    Mirror,
+}
+
+impl<T> DynamicGrid<T> {
+    /// Convolve the grid with the `kernel`, centered on each cell
+    ///
+    /// The kernel is centered at col `kernel.width() / 2` and row `kernel.height() / 2`,
+    /// and is applied without being flipped (which makes no difference for symmetric kernels).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, EdgeMode};
+    /// let grid = DynamicGrid::new_from_iter(3, [0, 0, 0, 0, 1, 0, 0, 0, 0]).unwrap();
+    /// let kernel = DynamicGrid::new_from_iter(3, [0, 1, 0, 1, 1, 1, 0, 1, 0]).unwrap();
+    /// let result: DynamicGrid<i32> = grid.convolve(&kernel, EdgeMode::Ignore);
+    /// assert_eq!(result, kernel);
+    /// ```
+    #[must_use]
+    pub fn convolve<K, O>(&self, kernel: &DynamicGrid<K>, edge: EdgeMode) -> DynamicGrid<O>
+    where
+        T: Copy + Mul<K, Output = O>,
+        K: Copy,
+        O: Default + AddAssign,
+    {
+        let (anchor_x, anchor_y) = (
+            to_isize(kernel.width / 2),
+            to_isize(kernel.height_or_zero() / 2),
+        );
+        DynamicGrid::new_with(self.width, self.height_or_zero(), |x, y| {
+            let mut sum = O::default();
+            for ((kx, ky), weight) in kernel.cells_with_coords() {
+                let dx = to_isize(kx) - anchor_x;
+                let dy = to_isize(ky) - anchor_y;
+                if let Some(cell) = self.sample(x, y, dx, dy, edge) {
+                    sum += *cell * *weight;
+                }
+            }
+            sum
+        })
+    }
+
+    /// Convolve the grid with a separable kernel, defined by its `horizontal` and `vertical` components
+    ///
+    /// This gives the same result as [`Self::convolve`] with a kernel where each cell is the product of
+    /// the corresponding `horizontal` and `vertical` weights, but is much faster for large kernels.
+    #[must_use]
+    pub fn convolve_separable<K, O>(
+        &self,
+        horizontal: &[K],
+        vertical: &[K],
+        edge: EdgeMode,
+    ) -> DynamicGrid<O>
+    where
+        T: Copy + Mul<K, Output = O>,
+        K: Copy,
+        O: Copy + Default + AddAssign + Mul<K, Output = O>,
+    {
+        let rows: DynamicGrid<O> =
+            DynamicGrid::new_with(self.width, self.height_or_zero(), |x, y| {
+                let anchor = to_isize(horizontal.len() / 2);
+                let mut sum = O::default();
+                for (i, weight) in horizontal.iter().enumerate() {
+                    if let Some(cell) = self.sample(x, y, to_isize(i) - anchor, 0, edge) {
+                        sum += *cell * *weight;
+                    }
+                }
+                sum
+            });
+        let anchor = to_isize(vertical.len() / 2);
+        DynamicGrid::new_with(self.width, self.height_or_zero(), |x, y| {
+            let mut sum = O::default();
+            for (i, weight) in vertical.iter().enumerate() {
+                if let Some(value) = rows.sample(x, y, 0, to_isize(i) - anchor, edge) {
+                    sum += *value * *weight;
+                }
+            }
+            sum
+        })
+    }
+
+    /// Replace each cell by the minimum of the square window of the given `radius` around it
+    ///
+    /// A `radius` larger than the width and height of the grid is clamped to the largest of them.
+    #[must_use]
+    pub fn min_filter(&self, radius: usize, edge: EdgeMode) -> DynamicGrid<T>
+    where
+        T: Ord + Copy,
+    {
+        self.window_filter(radius, edge, |window| window.iter().min().copied())
+    }
+
+    /// Replace each cell by the maximum of the square window of the given `radius` around it
+    ///
+    /// A `radius` larger than the width and height of the grid is clamped to the largest of them.
+    #[must_use]
+    pub fn max_filter(&self, radius: usize, edge: EdgeMode) -> DynamicGrid<T>
+    where
+        T: Ord + Copy,
+    {
+        self.window_filter(radius, edge, |window| window.iter().max().copied())
+    }
+
+    /// Replace each cell by the median of the square window of the given `radius` around it
+    ///
+    /// When the window contains an even number of cells, the upper median is used.
+    /// A `radius` larger than the width and height of the grid is clamped to the largest of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, EdgeMode};
+    /// let grid = DynamicGrid::new_from_iter(5, [1, 1, 9, 1, 1]).unwrap();
+    /// let filtered = grid.median_filter(1, EdgeMode::Clamp);
+    /// assert!(filtered.cells().all(|c| *c == 1));
+    /// ```
+    #[must_use]
+    pub fn median_filter(&self, radius: usize, edge: EdgeMode) -> DynamicGrid<T>
+    where
+        T: Ord + Copy,
+    {
+        self.window_filter(radius, edge, |window| {
+            let middle = window.len() / 2;
+            window.select_nth_unstable(middle);
+            window.get(middle).copied()
+        })
+    }
+
+    fn window_filter(
+        &self,
+        radius: usize,
+        edge: EdgeMode,
+        mut reduce: impl FnMut(&mut [T]) -> Option<T>,
+    ) -> DynamicGrid<T>
+    where
+        T: Copy,
+    {
+        let radius = to_isize(self.clamp_radius(radius));
+        let mut window = Vec::new();
+        DynamicGrid::new_with(self.width, self.height_or_zero(), |x, y| {
+            window.clear();
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    window.extend(self.sample(x, y, dx, dy, edge).copied());
+                }
+            }
+            reduce(&mut window).unwrap_or(self.cells[y * self.width + x])
+        })
+    }
+
+    /// Returns the `radius` clamped to the largest of the width and height of the grid
+    ///
+    /// A window wider than the grid already covers all the cells (or the edges) of the grid.
+    fn clamp_radius(&self, radius: usize) -> usize {
+        radius.min(self.width.max(self.height_or_zero()))
+    }
+
+    /// Returns the cell at the given offset from the cell at col `x` and row `y`, according to the `edge` mode
+    fn sample(&self, x: usize, y: usize, dx: isize, dy: isize, edge: EdgeMode) -> Option<&T> {
+        let x = resolve(to_isize(x) + dx, self.width, edge)?;
+        let y = resolve(to_isize(y) + dy, self.height_or_zero(), edge)?;
+        self.get(x, y)
+    }
+}
+
+impl DynamicGrid<f32> {
+    /// Blur the grid by averaging each cell with the square window of the given `radius` around it
+    ///
+    /// A `radius` larger than the width and height of the grid is clamped to the largest of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, EdgeMode};
+    /// let grid = DynamicGrid::new_from_iter(3, [0.0, 3.0, 0.0]).unwrap();
+    /// let blurred = grid.box_blur(1, EdgeMode::Clamp);
+    /// assert_eq!(blurred.cells().copied().collect::<Vec<_>>(), [1.0, 1.0, 1.0]);
+    /// ```
+    #[must_use]
+    pub fn box_blur(&self, radius: usize, edge: EdgeMode) -> DynamicGrid<f32> {
+        let size = 2 * self.clamp_radius(radius) + 1;
+        let weight = 1.0 / as_f32(size);
+        let weights: Vec<f32> = (0..size).map(|_| weight).collect();
+        self.convolve_separable(&weights, &weights, edge)
+    }
+
+    /// Blur the grid with a gaussian kernel of standard deviation `sigma` (in cells)
+    ///
+    /// The kernel is truncated at three standard deviations (or at the largest of the width and height of the grid),
+    /// and normalized.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn gaussian_blur(&self, sigma: f32, edge: EdgeMode) -> DynamicGrid<f32> {
+        if sigma <= 0.0 || !sigma.is_finite() {
+            return self.clone();
+        }
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let radius = self.clamp_radius((3.0 * sigma).ceil() as usize);
+        let mut weights: Vec<f32> = (0..=2 * radius)
+            .map(|i| {
+                let x = as_f32(i) - as_f32(radius);
+                (-(x * x) / (2.0 * sigma * sigma)).exp()
+            })
+            .collect();
+        let total: f32 = weights.iter().sum();
+        for weight in &mut weights {
+            *weight /= total;
+        }
+        self.convolve_separable(&weights, &weights, edge)
+    }
+}
+
+impl DynamicGrid<bool> {
+    /// Morphological erosion: a cell remains `true` only if all its neighbors are `true`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, EdgeMode, Neighborhood};
+    /// let grid = DynamicGrid::new_with(5, 5, |x, y| (1..4).contains(&x) && (1..4).contains(&y));
+    /// let eroded = grid.erode(Neighborhood::Moore, EdgeMode::Clamp);
+    /// assert_eq!(eroded, DynamicGrid::new_with(5, 5, |x, y| (x, y) == (2, 2)));
+    /// ```
+    #[must_use]
+    pub fn erode(&self, neighborhood: Neighborhood, edge: EdgeMode) -> DynamicGrid<bool> {
+        self.morphology(neighborhood, edge, true)
+    }
+
+    /// Morphological dilation: a cell becomes `true` if any of its neighbors is `true`
+    #[must_use]
+    pub fn dilate(&self, neighborhood: Neighborhood, edge: EdgeMode) -> DynamicGrid<bool> {
+        self.morphology(neighborhood, edge, false)
+    }
+
+    /// Morphological opening (erosion followed by dilation): removes small `true` regions and thin protrusions
+    #[must_use]
+    pub fn open(&self, neighborhood: Neighborhood, edge: EdgeMode) -> DynamicGrid<bool> {
+        self.erode(neighborhood, edge).dilate(neighborhood, edge)
+    }
+
+    /// Morphological closing (dilation followed by erosion): fills small `false` holes and thin gaps
+    #[must_use]
+    pub fn close(&self, neighborhood: Neighborhood, edge: EdgeMode) -> DynamicGrid<bool> {
+        self.dilate(neighborhood, edge).erode(neighborhood, edge)
+    }
+
+    fn morphology(
+        &self,
+        neighborhood: Neighborhood,
+        edge: EdgeMode,
+        erode: bool,
+    ) -> DynamicGrid<bool> {
+        DynamicGrid::new_with(self.width, self.height_or_zero(), |x, y| {
+            let cell = self.cells[y * self.width + x];
+            let mut neighbors = neighborhood
+                .offsets()
+                .iter()
+                .filter_map(|&(dx, dy)| self.sample(x, y, isize::from(dx), isize::from(dy), edge));
+            if erode {
+                cell && neighbors.all(|c| *c)
+            } else {
+                cell || neighbors.any(|c| *c)
+            }
+        })
+    }
+}
+
+fn resolve(value: isize, size: usize, edge: EdgeMode) -> Option<usize> {
+    let size = to_isize(size);
+    if size == 0 {
+        return None;
+    }
+    let resolved = match edge {
+        EdgeMode::Ignore => value,
+        EdgeMode::Clamp => value.clamp(0, size - 1),
+        EdgeMode::Wrap => value.rem_euclid(size),
+        EdgeMode::Mirror => {
+            let folded = value.rem_euclid(2 * size);
+            if folded >= size {
+                2 * size - 1 - folded
+            } else {
+                folded
+            }
+        }
+    };
+    usize::try_from(resolved)
+        .ok()
+        .filter(|v| to_isize(*v) < size)
+}
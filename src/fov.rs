@@ -0,0 +1,279 @@
+use alloc::vec;
+
+use crate::{numeric::to_i64, DynamicGrid};
+
+/// Algorithm used to compute a field of view
+///
+/// All algorithms are variants of shadowcasting, and only differ by which floor cells (cells that do not block sight)
+/// are considered visible when they are partially in shadow.
+///
+/// Shadows are cast row by row, moving away from the origin, and light is only sampled on the line through the centers
+/// of the cells of a row. On that line, a cell has three sample points: its center, and the midpoints of the two edges
+/// it shares with its neighbors in the row.
+/// Cells blocking sight are visible as soon as the lit part of that line reaches them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum FovAlgorithm {
+    /// A floor cell is visible if its center is lit
+    ///
+    /// This is symmetric: if a floor cell `a` sees a floor cell `b`, then `b` also sees `a`.
+    SymmetricShadowcasting,
+    /// A floor cell is visible if any of its three sample points is lit (its center or one of its edge midpoints)
+    Permissive,
+    /// A floor cell is visible if its center and at least one of its two edge midpoints are lit
+    RestrictedPrecision,
+}
+
+/// Accumulates what has been seen by field of view computations
+///
+/// # Example
+///
+/// ```
+/// # use cell_grid::{DynamicGrid, FogOfWar, FovAlgorithm};
+/// let grid = DynamicGrid::new_from_iter(5, "..#..".chars()).unwrap();
+/// let mut fog = FogOfWar::new(5, 1);
+/// fog.update(&grid, (0, 0), 10, FovAlgorithm::SymmetricShadowcasting, |c| *c == '#');
+/// fog.update(&grid, (4, 0), 10, FovAlgorithm::SymmetricShadowcasting, |c| *c == '#');
+/// assert!(!fog.is_visible(0, 0));
+/// assert!(fog.is_remembered(0, 0));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FogOfWar {
+    visible: DynamicGrid<bool>,
+    remembered: DynamicGrid<bool>,
+}
+
+impl<T> DynamicGrid<T> {
+    /// Compute the cells visible from `origin` within `radius`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, FovAlgorithm};
+    /// let grid = DynamicGrid::new_from_iter(5, "..#..".chars()).unwrap();
+    /// let visible = grid.field_of_view((0, 0), 10, FovAlgorithm::SymmetricShadowcasting, |c| *c == '#');
+    /// assert_eq!(visible.cells().copied().collect::<Vec<_>>(), [true, true, true, false, false]);
+    /// ```
+    #[must_use]
+    pub fn field_of_view(
+        &self,
+        origin: (usize, usize),
+        radius: usize,
+        algorithm: FovAlgorithm,
+        blocks_sight: impl FnMut(&T) -> bool,
+    ) -> DynamicGrid<bool> {
+        let mut visible = DynamicGrid {
+            cells: vec![false; self.cells.len()],
+            width: self.width,
+        };
+        self.cast_shadows(origin, radius, algorithm, blocks_sight, |index| {
+            visible.cells[index] = true;
+        });
+        visible
+    }
+
+    fn cast_shadows(
+        &self,
+        origin: (usize, usize),
+        radius: usize,
+        algorithm: FovAlgorithm,
+        mut blocks_sight: impl FnMut(&T) -> bool,
+        mut reveal: impl FnMut(usize),
+    ) {
+        let origin_index = match self.index(origin.0, origin.1) {
+            Some(index) if index < self.cells.len() => index,
+            _ => return,
+        };
+        reveal(origin_index);
+        let (ox, oy) = (to_i64(origin.0), to_i64(origin.1));
+        let (width, height) = (to_i64(self.width), to_i64(self.height()));
+        let radius = to_i64(radius);
+        let radius_squared = radius.saturating_mul(radius);
+        for quadrant in 0..4 {
+            let transform = |depth: i64, col: i64| -> Option<usize> {
+                let (x, y) = match quadrant {
+                    0 => (ox + col, oy - depth),
+                    1 => (ox + depth, oy + col),
+                    2 => (ox + col, oy + depth),
+                    _ => (ox - depth, oy + col),
+                };
+                if x < 0 || y < 0 || x >= width || y >= height {
+                    None
+                } else {
+                    usize::try_from(y * width + x).ok()
+                }
+            };
+            let mut rows = vec![Row {
+                depth: 1,
+                start: (-1, 1),
+                end: (1, 1),
+            }];
+            while let Some(mut row) = rows.pop() {
+                if row.depth > radius {
+                    continue;
+                }
+                let mut previous_blocks: Option<bool> = None;
+                for col in row.min_col()..=row.max_col() {
+                    let index = transform(row.depth, col);
+                    let blocks = index.map_or(true, |i| blocks_sight(&self.cells[i]));
+                    let in_radius = col * col + row.depth * row.depth <= radius_squared;
+                    if let (Some(index), true) = (index, in_radius) {
+                        if blocks || row.is_visible(col, algorithm) {
+                            reveal(index);
+                        }
+                    }
+                    if previous_blocks == Some(true) && !blocks {
+                        row.start = (2 * col - 1, 2 * row.depth);
+                    }
+                    if previous_blocks == Some(false) && blocks {
+                        rows.push(Row {
+                            depth: row.depth + 1,
+                            start: row.start,
+                            end: (2 * col - 1, 2 * row.depth),
+                        });
+                    }
+                    previous_blocks = Some(blocks);
+                }
+                if previous_blocks == Some(false) {
+                    rows.push(Row {
+                        depth: row.depth + 1,
+                        start: row.start,
+                        end: row.end,
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl FogOfWar {
+    /// Create a fog of war for a grid of the given size, where nothing is visible nor remembered
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            visible: DynamicGrid::new_with(width, height, |_, _| false),
+            remembered: DynamicGrid::new_with(width, height, |_, _| false),
+        }
+    }
+
+    /// Replace the visible cells by the ones visible from `origin`, and remember them
+    ///
+    /// See [`DynamicGrid::field_of_view`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of `grid` does not match the size of the fog of war
+    pub fn update<T>(
+        &mut self,
+        grid: &DynamicGrid<T>,
+        origin: (usize, usize),
+        radius: usize,
+        algorithm: FovAlgorithm,
+        blocks_sight: impl FnMut(&T) -> bool,
+    ) {
+        self.visible.cells_mut().for_each(|c| *c = false);
+        self.add_viewer(grid, origin, radius, algorithm, blocks_sight);
+    }
+
+    /// Add the cells visible from `origin` to the visible cells, and remember them
+    ///
+    /// This is useful when there are multiple viewers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of `grid` does not match the size of the fog of war
+    pub fn add_viewer<T>(
+        &mut self,
+        grid: &DynamicGrid<T>,
+        origin: (usize, usize),
+        radius: usize,
+        algorithm: FovAlgorithm,
+        blocks_sight: impl FnMut(&T) -> bool,
+    ) {
+        assert!(
+            grid.cells.len() == self.visible.cells.len()
+                && (grid.width == self.visible.width || grid.cells.is_empty()),
+            "the grid size does not match the fog of war size"
+        );
+        let Self {
+            visible,
+            remembered,
+        } = self;
+        grid.cast_shadows(origin, radius, algorithm, blocks_sight, |index| {
+            visible.cells[index] = true;
+            remembered.cells[index] = true;
+        });
+    }
+
+    /// Forget everything that was seen, including the currently visible cells
+    pub fn forget(&mut self) {
+        self.visible.cells_mut().for_each(|c| *c = false);
+        self.remembered.cells_mut().for_each(|c| *c = false);
+    }
+
+    /// Returns `true` if the cell at col `x` and row `y` is currently visible
+    #[must_use]
+    pub fn is_visible(&self, x: usize, y: usize) -> bool {
+        self.visible.get(x, y).copied().unwrap_or(false)
+    }
+
+    /// Returns `true` if the cell at col `x` and row `y` has been visible at some point
+    #[must_use]
+    pub fn is_remembered(&self, x: usize, y: usize) -> bool {
+        self.remembered.get(x, y).copied().unwrap_or(false)
+    }
+
+    /// Returns the grid of currently visible cells
+    #[must_use]
+    pub fn visible(&self) -> &DynamicGrid<bool> {
+        &self.visible
+    }
+
+    /// Returns the grid of cells that have been visible at some point
+    #[must_use]
+    pub fn remembered(&self) -> &DynamicGrid<bool> {
+        &self.remembered
+    }
+}
+
+/// A row of cells being scanned, at a given `depth` from the origin, and between `start` and `end` slopes
+///
+/// Slopes are fractions `(numerator, denominator)` with a positive denominator.
+struct Row {
+    depth: i64,
+    start: (i64, i64),
+    end: (i64, i64),
+}
+
+impl Row {
+    fn min_col(&self) -> i64 {
+        let (n, d) = self.start;
+        floor_div(2 * self.depth * n + d, 2 * d)
+    }
+
+    fn max_col(&self) -> i64 {
+        let (n, d) = self.end;
+        -floor_div(-(2 * self.depth * n - d), 2 * d)
+    }
+
+    /// Returns `true` if the point at `half_col / 2` on the center line of this row is lit
+    fn is_lit(&self, half_col: i64) -> bool {
+        let (sn, sd) = self.start;
+        let (en, ed) = self.end;
+        half_col * sd >= 2 * self.depth * sn && half_col * ed <= 2 * self.depth * en
+    }
+
+    fn is_visible(&self, col: i64, algorithm: FovAlgorithm) -> bool {
+        let center = self.is_lit(2 * col);
+        let edges = [self.is_lit(2 * col - 1), self.is_lit(2 * col + 1)];
+        match algorithm {
+            FovAlgorithm::SymmetricShadowcasting => center,
+            FovAlgorithm::Permissive => center || edges.contains(&true),
+            FovAlgorithm::RestrictedPrecision => center && edges.contains(&true),
+        }
+    }
+}
+
+fn floor_div(a: i64, b: i64) -> i64 {
+    a.div_euclid(b)
+}
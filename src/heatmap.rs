@@ -0,0 +1,180 @@
+use crate::{
+    numeric::{as_f64, round_to_u8, truncate},
+    DynamicGrid,
+};
+
+const VIRIDIS: [[u8; 3]; 11] = [
+    [68, 1, 84],
+    [72, 36, 117],
+    [65, 68, 135],
+    [53, 95, 141],
+    [42, 120, 142],
+    [33, 145, 140],
+    [34, 168, 132],
+    [68, 191, 112],
+    [122, 209, 81],
+    [189, 223, 38],
+    [253, 231, 37],
+];
+
+const MAGMA: [[u8; 3]; 11] = [
+    [0, 0, 4],
+    [20, 14, 54],
+    [59, 15, 112],
+    [100, 26, 128],
+    [140, 41, 129],
+    [183, 55, 121],
+    [222, 73, 104],
+    [247, 112, 92],
+    [254, 159, 109],
+    [254, 207, 146],
+    [252, 253, 191],
+];
+
+/// Colormap of a heatmap, see [`DynamicGrid::heatmap`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Colormap {
+    /// Perceptually uniform colormap from dark purple to yellow
+    Viridis,
+    /// Perceptually uniform colormap from black to light yellow, through purple and orange
+    Magma,
+    /// From black to white
+    Grayscale,
+}
+
+/// Options of [`DynamicGrid::heatmap`]
+///
+/// # Example
+///
+/// ```
+/// # use cell_grid::{Colormap, HeatmapOptions};
+/// let options = HeatmapOptions::default().colormap(Colormap::Magma).range(0.0, 10.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatmapOptions {
+    colormap: Colormap,
+    range: Option<(f64, f64)>,
+    nan_color: [u8; 3],
+}
+
+impl Colormap {
+    /// Returns the color at `t`, from `0.0` (start of the colormap) to `1.0` (end of the colormap)
+    ///
+    /// `t` is clamped between `0.0` and `1.0`, and `NaN` is treated as `0.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::Colormap;
+    /// assert_eq!(Colormap::Grayscale.color(0.5), [128, 128, 128]);
+    /// assert_eq!(Colormap::Viridis.color(1.0), [253, 231, 37]);
+    /// ```
+    #[must_use]
+    pub fn color(self, t: f64) -> [u8; 3] {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let stops: &[[u8; 3]] = match self {
+            Self::Viridis => &VIRIDIS,
+            Self::Magma => &MAGMA,
+            Self::Grayscale => &[[0, 0, 0], [255, 255, 255]],
+        };
+        let scaled = t * as_f64(stops.len() - 1);
+        let index = truncate(scaled).min(stops.len() - 2);
+        let fraction = scaled - as_f64(index);
+        let (start, end) = (stops[index], stops[index + 1]);
+        let mut color = [0; 3];
+        for channel in 0..3 {
+            let start = f64::from(start[channel]);
+            let end = f64::from(end[channel]);
+            color[channel] = round_to_u8(start + (end - start) * fraction);
+        }
+        color
+    }
+}
+
+impl Default for HeatmapOptions {
+    fn default() -> Self {
+        Self {
+            colormap: Colormap::Viridis,
+            range: None,
+            nan_color: [255, 0, 255],
+        }
+    }
+}
+
+impl HeatmapOptions {
+    /// Set the colormap ([`Colormap::Viridis`] by default)
+    #[must_use]
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// Use a fixed range of values, where `min` is the start of the colormap, and `max` the end of it
+    ///
+    /// Values outside of the range are clamped, and `min` may be greater than `max` to reverse the colormap.
+    /// By default, the range goes from the smallest to the greatest finite value of the grid.
+    #[must_use]
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Set the color of the `NaN` cells (magenta by default)
+    #[must_use]
+    pub fn nan_color(mut self, color: [u8; 3]) -> Self {
+        self.nan_color = color;
+        self
+    }
+}
+
+impl<T: Copy + Into<f64>> DynamicGrid<T> {
+    /// Render the grid as a heatmap, where each cell is an RGB color
+    ///
+    /// The result can be saved as an image with [`DynamicGrid::to_ppm`] (or `to_png` with the `png` feature).
+    ///
+    /// If all the cells have the same value, they get the color of the start of the colormap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{Colormap, DynamicGrid, HeatmapOptions};
+    /// let grid = DynamicGrid::new_from_iter(2, [0.0_f32, 5.0, 10.0, f32::NAN]).unwrap();
+    /// let heatmap = grid.heatmap(&HeatmapOptions::default().colormap(Colormap::Grayscale));
+    /// assert_eq!(heatmap.get(0, 0), Some(&[0, 0, 0]));
+    /// assert_eq!(heatmap.get(1, 0), Some(&[128, 128, 128]));
+    /// assert_eq!(heatmap.get(0, 1), Some(&[255, 255, 255]));
+    /// assert_eq!(heatmap.get(1, 1), Some(&[255, 0, 255]));
+    /// ```
+    #[must_use]
+    pub fn heatmap(&self, options: &HeatmapOptions) -> DynamicGrid<[u8; 3]> {
+        let (min, max) = options.range.unwrap_or_else(|| {
+            self.cells
+                .iter()
+                .map(|&cell| cell.into())
+                .filter(|value: &f64| value.is_finite())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                    (min.min(value), max.max(value))
+                })
+        });
+        let span = max - min;
+        let cells = self
+            .cells
+            .iter()
+            .map(|&cell| {
+                let value: f64 = cell.into();
+                if value.is_nan() {
+                    options.nan_color
+                } else if span.is_normal() {
+                    options.colormap.color((value - min) / span)
+                } else {
+                    options.colormap.color(if value > min { 1.0 } else { 0.0 })
+                }
+            })
+            .collect();
+        DynamicGrid {
+            cells,
+            width: self.width,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Reading of the fields of the JSON formats of Tiled and LDtk
+
+use alloc::{format, string::String};
+use serde_json::Value;
+
+/// Error returned when a field is missing or has an unexpected type
+///
+/// It is converted into the error type of the format being read.
+pub(crate) struct InvalidField {
+    pub(crate) message: String,
+}
+
+/// Read a required integer field
+pub(crate) fn number<V: TryFrom<i64>>(value: &Value, name: &str) -> Result<V, InvalidField> {
+    value
+        .get(name)
+        .and_then(Value::as_i64)
+        .and_then(|number| V::try_from(number).ok())
+        .ok_or_else(|| InvalidField::missing(name))
+}
+
+/// Read a required field which is an array of two integers
+#[cfg(feature = "ldtk")]
+pub(crate) fn pair<V: TryFrom<i64>>(value: &Value, name: &str) -> Result<[V; 2], InvalidField> {
+    let values = match value.get(name) {
+        Some(Value::Array(values)) if values.len() == 2 => values,
+        _ => return Err(InvalidField::missing(name)),
+    };
+    let component = |value: &Value| {
+        value
+            .as_i64()
+            .and_then(|number| V::try_from(number).ok())
+            .ok_or_else(|| InvalidField::missing(name))
+    };
+    Ok([component(&values[0])?, component(&values[1])?])
+}
+
+/// Read an optional string field
+pub(crate) fn string(value: &Value, name: &str) -> Option<String> {
+    value.get(name).and_then(Value::as_str).map(String::from)
+}
+
+/// Read an optional array field, which is empty if it is missing or `null`
+pub(crate) fn array<'a>(value: &'a Value, name: &str) -> Result<&'a [Value], InvalidField> {
+    match value.get(name) {
+        Some(Value::Array(values)) => Ok(values),
+        None | Some(Value::Null) => Ok(&[]),
+        Some(_) => Err(InvalidField {
+            message: format!("invalid field {name}"),
+        }),
+    }
+}
+
+impl InvalidField {
+    fn missing(name: &str) -> Self {
+        Self {
+            message: format!("missing or invalid field {name}"),
+        }
+    }
+}
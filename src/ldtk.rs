@@ -0,0 +1,304 @@
+//! Import of [LDtk](https://ldtk.io) projects and levels
+//!
+//! IntGrid layers become a [`DynamicGrid<u32>`] (where `0` is an empty cell),
+//! and the tiles of auto-layers, tile layers and IntGrid layers with auto-layer rules become a [`DynamicGrid<Option<Tile>>`].
+//! Entity layers are ignored.
+//!
+//! When several tiles are stacked in a cell, the one drawn last (on top) is kept.
+//!
+//! # Example
+//!
+//! ```
+//! # use cell_grid::ldtk::{LayerKind, Project};
+//! let json = r#"{
+//!   "levels": [{
+//!     "identifier": "Level_0", "worldX": 256, "worldY": 0, "pxWid": 32, "pxHei": 16,
+//!     "layerInstances": [{
+//!       "__identifier": "Walls", "__type": "IntGrid", "__cWid": 2, "__cHei": 1, "__gridSize": 16,
+//!       "__pxTotalOffsetX": 0, "__pxTotalOffsetY": 0, "__tilesetDefUid": null,
+//!       "intGridCsv": [1, 0], "autoLayerTiles": [], "gridTiles": []
+//!     }]
+//!   }]
+//! }"#;
+//! let project = Project::from_json(json).unwrap();
+//! let level = &project.levels[0];
+//! let walls = &level.layers[0];
+//! assert_eq!(walls.kind, LayerKind::IntGrid);
+//! assert_eq!(walls.int_grid.as_ref().unwrap().get(0, 0), Some(&1));
+//! assert_eq!(level.cell_to_world(walls, 1, 0), [272, 0]);
+//! ```
+
+use core::fmt::Display;
+
+use alloc::{format, string::String, vec::Vec};
+use serde_json::Value;
+
+use crate::{
+    json::{array, number, pair, string, InvalidField},
+    numeric::to_i64,
+    DynamicGrid,
+};
+
+/// Maximum number of cells of a layer
+const MAX_LAYER_CELLS: usize = 1 << 26;
+
+/// An LDtk project
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Project {
+    /// Levels of the project, including the levels of all the worlds
+    pub levels: Vec<Level>,
+}
+
+/// A level of an LDtk [`Project`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Level {
+    /// Name of the level
+    pub identifier: String,
+    /// Horizontal position of the level in the world, in pixels
+    ///
+    /// LDtk sets it to `-1` in the linear world layouts.
+    pub world_x: i32,
+    /// Vertical position of the level in the world, in pixels
+    ///
+    /// LDtk sets it to `-1` in the linear world layouts.
+    pub world_y: i32,
+    /// Width of the level, in pixels
+    pub width: u32,
+    /// Height of the level, in pixels
+    pub height: u32,
+    /// Path to the level file (relative to the project), if it is saved separately
+    ///
+    /// The layers of such a level are not in the project file, and can be read with [`Level::from_json`].
+    pub external_path: Option<String>,
+    /// Layers of the level, from top to bottom (as ordered by LDtk)
+    pub layers: Vec<Layer>,
+}
+
+/// A layer of a [`Level`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Layer {
+    /// Name of the layer
+    pub identifier: String,
+    /// Kind of the layer
+    pub kind: LayerKind,
+    /// Size of a cell, in pixels
+    pub grid_size: u32,
+    /// Offset of the layer relative to the level, in pixels
+    pub offset: [i32; 2],
+    /// IntGrid values (`0` for empty cells), for IntGrid layers
+    pub int_grid: Option<DynamicGrid<u32>>,
+    /// Tiles, for layers which have a tileset
+    pub tiles: Option<DynamicGrid<Option<Tile>>>,
+}
+
+/// Kind of a [`Layer`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum LayerKind {
+    /// IntGrid layer, which may also have auto-layer tiles
+    IntGrid,
+    /// Auto-layer, whose tiles are generated by the rules from another IntGrid layer
+    AutoLayer,
+    /// Layer of manually placed tiles
+    Tiles,
+}
+
+/// A tile placed in a [`Layer`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct Tile {
+    /// Id of the tile in the tileset of the layer
+    pub id: u32,
+    /// Whether the tile is flipped horizontally
+    pub flip_x: bool,
+    /// Whether the tile is flipped vertically
+    pub flip_y: bool,
+}
+
+/// Error returned when an LDtk project or level cannot be imported
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct LdtkError {
+    kind: LdtkErrorKind,
+}
+
+#[derive(Debug)]
+enum LdtkErrorKind {
+    Json(serde_json::Error),
+    Invalid(String),
+}
+
+impl Project {
+    /// Import a project from the content of an `.ldtk` file
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`LdtkError`] if the JSON is invalid, or if a layer is invalid or has more than 2<sup>26</sup> cells.
+    pub fn from_json(json: &str) -> Result<Self, LdtkError> {
+        let root: Value = serde_json::from_str(json).map_err(LdtkError::json)?;
+        let mut levels = Vec::new();
+        for level in array(&root, "levels")? {
+            levels.push(read_level(level)?);
+        }
+        for world in array(&root, "worlds")? {
+            for level in array(world, "levels")? {
+                levels.push(read_level(level)?);
+            }
+        }
+        Ok(Self { levels })
+    }
+}
+
+impl Level {
+    /// Import a level from the content of an `.ldtkl` file (a level saved separately from its project)
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`LdtkError`] if the JSON is invalid, or if a layer is invalid or has more than 2<sup>26</sup> cells.
+    pub fn from_json(json: &str) -> Result<Self, LdtkError> {
+        let root: Value = serde_json::from_str(json).map_err(LdtkError::json)?;
+        read_level(&root)
+    }
+
+    /// Returns the position in the world, in pixels, of the top-left corner of the cell at (`x`, `y`) in `layer`
+    #[must_use]
+    pub fn cell_to_world(&self, layer: &Layer, x: usize, y: usize) -> [i64; 2] {
+        let grid_size = i64::from(layer.grid_size);
+        [
+            i64::from(self.world_x) + i64::from(layer.offset[0]) + to_i64(x) * grid_size,
+            i64::from(self.world_y) + i64::from(layer.offset[1]) + to_i64(y) * grid_size,
+        ]
+    }
+}
+
+fn read_level(value: &Value) -> Result<Level, LdtkError> {
+    let mut layers = Vec::new();
+    for layer in array(value, "layerInstances")? {
+        if let Some(layer) = read_layer(layer)? {
+            layers.push(layer);
+        }
+    }
+    Ok(Level {
+        identifier: string(value, "identifier").unwrap_or_default(),
+        world_x: number(value, "worldX")?,
+        world_y: number(value, "worldY")?,
+        width: number(value, "pxWid")?,
+        height: number(value, "pxHei")?,
+        external_path: string(value, "externalRelPath"),
+        layers,
+    })
+}
+
+fn read_layer(value: &Value) -> Result<Option<Layer>, LdtkError> {
+    let kind = match value.get("__type").and_then(Value::as_str) {
+        Some("IntGrid") => LayerKind::IntGrid,
+        Some("AutoLayer") => LayerKind::AutoLayer,
+        Some("Tiles") => LayerKind::Tiles,
+        _ => return Ok(None),
+    };
+    let identifier = string(value, "__identifier").unwrap_or_default();
+    let width: usize = number(value, "__cWid")?;
+    let height: usize = number(value, "__cHei")?;
+    let grid_size: u32 = number(value, "__gridSize")?;
+    let count = width
+        .checked_mul(height)
+        .filter(|&count| count <= MAX_LAYER_CELLS)
+        .ok_or_else(|| LdtkError::invalid(format!("layer {identifier} is too large")))?;
+    let int_grid = if kind == LayerKind::IntGrid {
+        let values = array(value, "intGridCsv")?
+            .iter()
+            .map(|value| {
+                value
+                    .as_u64()
+                    .and_then(|value| u32::try_from(value).ok())
+                    .ok_or_else(|| LdtkError::invalid(format!("invalid IntGrid value: {value}")))
+            })
+            .collect::<Result<Vec<u32>, LdtkError>>()?;
+        if values.len() != count {
+            return Err(LdtkError::invalid(format!(
+                "expected {count} IntGrid values in layer {identifier}, found {}",
+                values.len()
+            )));
+        }
+        Some(DynamicGrid {
+            cells: values,
+            width,
+        })
+    } else {
+        None
+    };
+    let tiles = if value.get("__tilesetDefUid").map_or(false, Value::is_number) {
+        let mut tiles = DynamicGrid::new_with(width, height, |_, _| None);
+        let cell_size: usize = number(value, "__gridSize")?;
+        let placed = array(value, "autoLayerTiles")?
+            .iter()
+            .chain(array(value, "gridTiles")?);
+        for tile in placed {
+            let [x, y]: [usize; 2] = pair(tile, "px")?;
+            let flags: u8 = number(tile, "f")?;
+            let placed_tile = Tile {
+                id: number(tile, "t")?,
+                flip_x: flags & 1 != 0,
+                flip_y: flags & 2 != 0,
+            };
+            if let Some(cell) = tiles.get_mut(x / cell_size.max(1), y / cell_size.max(1)) {
+                *cell = Some(placed_tile);
+            }
+        }
+        Some(tiles)
+    } else {
+        None
+    };
+    Ok(Some(Layer {
+        offset: [
+            number(value, "__pxTotalOffsetX").unwrap_or_default(),
+            number(value, "__pxTotalOffsetY").unwrap_or_default(),
+        ],
+        identifier,
+        kind,
+        grid_size,
+        int_grid,
+        tiles,
+    }))
+}
+
+impl LdtkError {
+    fn json(error: serde_json::Error) -> Self {
+        Self {
+            kind: LdtkErrorKind::Json(error),
+        }
+    }
+
+    fn invalid(message: impl Into<String>) -> Self {
+        Self {
+            kind: LdtkErrorKind::Invalid(message.into()),
+        }
+    }
+}
+
+impl From<InvalidField> for LdtkError {
+    fn from(field: InvalidField) -> Self {
+        Self::invalid(field.message)
+    }
+}
+
+impl Display for LdtkError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.kind {
+            LdtkErrorKind::Json(error) => write!(f, "Invalid JSON: {error}"),
+            LdtkErrorKind::Invalid(message) => write!(f, "Invalid LDtk project: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for LdtkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            LdtkErrorKind::Json(error) => Some(error),
+            LdtkErrorKind::Invalid(_) => None,
+        }
+    }
+}
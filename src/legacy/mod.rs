@@ -11,6 +11,7 @@ pub use rect::Rect;
 /// A 2d fixed-size grid containers for cells of type `T`
 #[deprecated(since = "0.1.4", note = "Use `DynamicGrid` instead")]
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Grid<T> {
     cells: Vec<T>,
     width: usize,
@@ -30,23 +30,109 @@
 //!
 //! * Create a grid from size and init function: [`DynamicGrid::new_with`]
 //! * Iterate the cells which overlap a rectangle: [`DynamicGrid::cells_in_rect`]
+//! * Compute dijkstra maps and flow fields: [`DynamicGrid::dijkstra_map`]
+//! * Incrementally replan paths as cells change: [`PathPlanner`]
+//! * Compute clearance for multi-size units: [`DynamicGrid::clearance`]
+//! * Compute the field of view from a cell: [`DynamicGrid::field_of_view`]
+//! * Iterate the cells on a line: [`DynamicGrid::cells_on_line`] and check line of sight: [`DynamicGrid::line_of_sight`]
+//! * Cast rays through the cells: [`DynamicGrid::raycast`]
+//! * Move boxes against solid cells: [`DynamicGrid::move_aabb`]
+//! * Run cellular automata: [`DynamicGrid::step_automaton`]
+//! * Apply convolutions and filters: [`DynamicGrid::convolve`], [`DynamicGrid::erode`], [`DynamicGrid::median_filter`]
+//! * Sum the cells in a rectangle in constant time: [`DynamicGrid::summed_area_table`]
+//! * Update cells and query rectangles in logarithmic time: [`DynamicGrid::fenwick_tree`] and [`DynamicGrid::segment_tree`]
+//! * Compute distance transforms and signed distance fields: [`DynamicGrid::nearest_feature`], [`DynamicGrid::manhattan_distance_transform`]
+//! * Extract contours and outlines as polylines: [`DynamicGrid::contours`] and [`DynamicGrid::outlines`]
+//! * Decompose regions into rectangles: [`DynamicGrid::decompose_into_rects`] and find the largest empty rectangle: [`DynamicGrid::largest_empty_rect`]
+//! * Label connected regions and find how they touch: [`DynamicGrid::regions`], and find chokepoints: [`DynamicGrid::chokepoints`]
+//! * Parse grids from text: [`DynamicGrid::parse_text`] and render them as text: [`DynamicGrid::display_with`]
+//! * Render grids as SVG images or HTML tables for debugging: [`DynamicGrid::to_svg`] and [`DynamicGrid::to_html_table`]
+//! * Render numeric grids as heatmaps: [`DynamicGrid::heatmap`]
+//! * Render grids in a terminal with ANSI colors or braille characters, and update live views incrementally: [`terminal`]
+//! * Read and write Netpbm images: [`DynamicGrid::from_pbm`], [`DynamicGrid::to_pgm`], [`DynamicGrid::to_ppm`]
+//! * Run-length encode the cells: [`DynamicGrid::encode_rle`], and save grids in a compact binary format: [`DynamicGrid::to_bytes`]
 //!
 //! ## Features
 //!
 //! * `std`: *(enabled by default)* enable use of the standard library. Must be disabled for `no_std` crates.
+//! * `rayon`: enable parallel computations with [rayon](https://docs.rs/rayon) (implies `std`)
+//! * `serde`: implement `Serialize` and `Deserialize` with [serde](https://serde.rs) (see also [`serde_rows`])
+//! * `csv`: read and write grids as CSV: [`DynamicGrid::read_csv`] and [`DynamicGrid::write_csv`] (implies `std`)
+//! * `png`: read and write grids as PNG images: [`DynamicGrid::from_png`] and [`DynamicGrid::to_png`] (implies `std`)
+//! * `tiled`: import and export [Tiled](https://www.mapeditor.org) maps in the TMX and JSON formats: [`tiled::Map`] (implies `std`)
+//! * `ldtk`: import [LDtk](https://ldtk.io) projects and levels: [`ldtk::Project`] (implies `std`)
 
 extern crate alloc;
 
+mod automaton;
+mod binary;
+mod clearance;
+pub mod collision;
+mod contour;
+mod decompose;
+#[cfg(feature = "csv")]
+mod delimited;
+mod dijkstra;
+mod distance;
 #[deprecated(
     since = "0.1.4",
     note = "The content of this module has been moved to the crate root"
 )]
 #[doc(hidden)]
 pub mod dynamic;
+mod filter;
+mod fov;
+mod heatmap;
+#[cfg(any(feature = "tiled", feature = "ldtk"))]
+mod json;
+#[cfg(feature = "ldtk")]
+pub mod ldtk;
 mod legacy;
+mod line;
+mod neighborhood;
+mod netpbm;
+mod numeric;
+mod planner;
+#[cfg(feature = "png")]
+mod png_image;
+mod range_query;
+mod raycast;
+mod region;
+mod render;
+#[cfg(feature = "serde")]
+pub mod serde_rows;
+#[cfg(feature = "serde")]
+mod serialization;
+mod summed_area;
+pub mod terminal;
+mod text;
+#[cfg(feature = "tiled")]
+pub mod tiled;
 
+pub use automaton::{Boundary, LifeRule, Neighbors, ParseLifeRuleError};
+pub use binary::{BinaryCell, Codec, DecodeBinaryError};
+pub use contour::Contour;
+pub use decompose::{CellRect, DecompositionMode};
+#[cfg(feature = "csv")]
+pub use delimited::{CsvError, CsvOptions};
+pub use dijkstra::Cost;
+pub use filter::EdgeMode;
+pub use fov::{FogOfWar, FovAlgorithm};
+pub use heatmap::{Colormap, HeatmapOptions};
 #[allow(deprecated)]
 pub use legacy::{Coord, Grid, Rect};
+pub use line::LineMode;
+pub use neighborhood::Neighborhood;
+pub use netpbm::ParseNetpbmError;
+pub use planner::PathPlanner;
+#[cfg(feature = "png")]
+pub use png_image::PngError;
+pub use range_query::{FenwickTree, SegmentTree};
+pub use raycast::RayHit;
+pub use region::{RegionAdjacency, Regions};
+pub use render::{CellStyle, SvgDisplay};
+pub use summed_area::SummedAreaTable;
+pub use text::TextDisplay;
 
 use core::{fmt::Display, mem};
 
@@ -139,8 +225,8 @@ impl<T> DynamicGrid<T> {
     ) -> Result<Self, IncompatibleRowSize> {
         let cells: Vec<T> = iter.into_iter().collect();
         if !cells.is_empty() && (width == 0 || cells.len() % width != 0) {
-            return Err(IncompatibleRowSize);
-        };
+            return Err(IncompatibleRowSize::UNKNOWN_LINE);
+        }
         Ok(Self { cells, width })
     }
 
@@ -161,7 +247,7 @@ impl<T> DynamicGrid<T> {
             self.width = self.cells.len();
         } else if self.width != self.cells.len() - old_len {
             self.cells.truncate(old_len);
-            return Err(IncompatibleRowSize);
+            return Err(IncompatibleRowSize::UNKNOWN_LINE);
         }
         Ok(())
     }
@@ -283,6 +369,14 @@ impl<T> DynamicGrid<T> {
         }
     }
 
+    fn height_or_zero(&self) -> usize {
+        if self.cells.is_empty() {
+            0
+        } else {
+            self.height()
+        }
+    }
+
     fn index_to_coord(index: usize, width: usize) -> (usize, usize) {
         (index % width, index / width)
     }
@@ -292,14 +386,34 @@ impl<T> DynamicGrid<T> {
 /// is incompatible with the current width of the grid
 #[derive(Debug, Clone)]
 #[non_exhaustive]
-pub struct IncompatibleRowSize;
+pub struct IncompatibleRowSize {
+    line: Option<usize>,
+}
+
+impl IncompatibleRowSize {
+    const UNKNOWN_LINE: Self = Self { line: None };
+
+    /// Returns the line number (starting at `1`) of the incompatible row, if it is known
+    ///
+    /// It is known when the error is returned by [`DynamicGrid::parse_text`].
+    #[must_use]
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+}
 
 impl Display for IncompatibleRowSize {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(
-            f,
-            "The row size is not compatible with the current content of the grid"
-        )
+        match self.line {
+            Some(line) => write!(
+                f,
+                "The row at line {line} is not compatible with the current content of the grid"
+            ),
+            None => write!(
+                f,
+                "The row size is not compatible with the current content of the grid"
+            ),
+        }
     }
 }
 
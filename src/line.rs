@@ -0,0 +1,413 @@
+use core::cmp::Ordering;
+
+use alloc::vec::Vec;
+
+use crate::{
+    numeric::{as_f32, floor, i64_as_f32, signum, to_i128},
+    DynamicGrid,
+};
+
+/// Algorithm used to select the cells on a line
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum LineMode {
+    /// Bresenham's line: exactly one cell per step along the major axis
+    Bresenham,
+    /// Every cell touched by the line, including both cells when the line passes exactly through a corner
+    Supercover,
+    /// Like [`LineMode::Bresenham`], but the line from `a` to `b` contains the same cells as the line from `b` to `a`
+    Symmetric,
+}
+
+impl<T> DynamicGrid<T> {
+    /// Returns an iterator over the coordinates of the cells on the line from `from` to `to` (both included)
+    ///
+    /// The line is clipped to the grid bounds: coordinates outside of the grid are never yielded.
+    /// Only the part of the line inside the grid is computed, so `from` and `to` may be arbitrarily far from the grid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, LineMode};
+    /// let grid = DynamicGrid::new_with(3, 3, |_, _| ());
+    /// let line: Vec<_> = grid.cells_on_line((0, 0), (5, 2), LineMode::Bresenham).collect();
+    /// assert_eq!(line, [(0, 0), (1, 0), (2, 1)]);
+    /// ```
+    #[must_use]
+    pub fn cells_on_line(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+        mode: LineMode,
+    ) -> impl DoubleEndedIterator<Item = (usize, usize)> {
+        let cells = self.line_cells(
+            (to_i128(from.0), to_i128(from.1)),
+            (to_i128(to.0), to_i128(to.1)),
+            mode,
+        );
+        cells.into_iter()
+    }
+
+    /// Returns an iterator over the coordinates of the cells on the line from `from` to `to` in world space
+    ///
+    /// In world space, the cell at col `x` and row `y` covers the area from `[x, y]` (included) to `[x + 1, y + 1]` (excluded).
+    ///
+    /// With [`LineMode::Supercover`], the exact segment is traversed.
+    /// With the other modes, the line goes between the cells containing `from` and `to`.
+    ///
+    /// The line is clipped to the grid bounds: coordinates outside of the grid are never yielded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, LineMode};
+    /// let grid = DynamicGrid::new_with(3, 3, |_, _| ());
+    /// let line: Vec<_> = grid.cells_on_line_f32([-1.5, 0.5], [1.5, 1.5], LineMode::Supercover).collect();
+    /// assert_eq!(line, [(0, 0), (0, 1), (1, 1)]);
+    /// ```
+    #[must_use]
+    pub fn cells_on_line_f32(
+        &self,
+        from: [f32; 2],
+        to: [f32; 2],
+        mode: LineMode,
+    ) -> impl DoubleEndedIterator<Item = (usize, usize)> {
+        let cells = if !from.iter().chain(&to).all(|v| v.is_finite()) {
+            Vec::new()
+        } else if mode == LineMode::Supercover {
+            self.supercover_f32(from, to)
+        } else {
+            self.line_cells(
+                (i128::from(floor(from[0])), i128::from(floor(from[1]))),
+                (i128::from(floor(to[0])), i128::from(floor(to[1]))),
+                mode,
+            )
+        };
+        cells.into_iter()
+    }
+
+    /// Returns `true` if no cell strictly between `from` and `to` blocks sight
+    ///
+    /// The lines are symmetric ([`LineMode::Symmetric`]), so that `a` sees `b` if and only if `b` sees `a`.
+    /// Returns `false` if `from` or `to` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_from_iter(5, "..#..".chars()).unwrap();
+    /// assert!(grid.line_of_sight((0, 0), (2, 0), |c| *c == '#'));
+    /// assert!(!grid.line_of_sight((0, 0), (3, 0), |c| *c == '#'));
+    /// ```
+    #[must_use]
+    pub fn line_of_sight(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+        blocks_sight: impl FnMut(&T) -> bool,
+    ) -> bool {
+        if self.get(from.0, from.1).is_none() || self.get(to.0, to.1).is_none() {
+            return false;
+        }
+        let cells = self.line_cells(
+            (to_i128(from.0), to_i128(from.1)),
+            (to_i128(to.0), to_i128(to.1)),
+            LineMode::Symmetric,
+        );
+        self.is_unobstructed(&cells, blocks_sight)
+    }
+
+    /// Returns `true` if no cell strictly between `from` and `to` in world space blocks sight
+    ///
+    /// The exact segment is traversed ([`LineMode::Supercover`]).
+    /// Returns `false` if `from` or `to` is out of bounds.
+    ///
+    /// See [`Self::cells_on_line_f32`] for the definition of the world space.
+    #[must_use]
+    pub fn line_of_sight_f32(
+        &self,
+        from: [f32; 2],
+        to: [f32; 2],
+        blocks_sight: impl FnMut(&T) -> bool,
+    ) -> bool {
+        let in_bounds = |p: [f32; 2]| {
+            p.iter().all(|v| v.is_finite()) && self.cell_index((floor(p[0]), floor(p[1]))).is_some()
+        };
+        if !in_bounds(from) || !in_bounds(to) {
+            return false;
+        }
+        self.is_unobstructed(&self.supercover_f32(from, to), blocks_sight)
+    }
+
+    fn is_unobstructed(
+        &self,
+        cells: &[(usize, usize)],
+        mut blocks_sight: impl FnMut(&T) -> bool,
+    ) -> bool {
+        let between = cells.len().saturating_sub(2);
+        cells
+            .iter()
+            .skip(1)
+            .take(between)
+            .all(|&(x, y)| self.get(x, y).map_or(false, |cell| !blocks_sight(cell)))
+    }
+
+    /// Returns the cells of the line between the cells `from` and `to` which are in the grid
+    ///
+    /// Only the steps of the line which are in the grid are computed, so that the line may be arbitrarily long.
+    fn line_cells(
+        &self,
+        from: (i128, i128),
+        to: (i128, i128),
+        mode: LineMode,
+    ) -> Vec<(usize, usize)> {
+        let size = (to_i128(self.width), to_i128(self.height_or_zero()));
+        match mode {
+            LineMode::Supercover => supercover(from, to, size),
+            LineMode::Symmetric if to < from => {
+                let mut cells = bresenham(to, from, size);
+                cells.reverse();
+                cells
+            }
+            LineMode::Bresenham | LineMode::Symmetric => bresenham(from, to, size),
+        }
+    }
+
+    /// Traverse every cell of the grid touched by the segment from `from` to `to` (Amanatides–Woo)
+    ///
+    /// The segment is first clipped to the grid (with a margin of one cell, to keep the cells touched through a corner).
+    fn supercover_f32(&self, from: [f32; 2], to: [f32; 2]) -> Vec<(usize, usize)> {
+        let size = [as_f32(self.width), as_f32(self.height_or_zero())];
+        let delta = [to[0] - from[0], to[1] - from[1]];
+        let at = |t: f32| [from[0] + delta[0] * t, from[1] + delta[1] * t];
+        // Parameters and positions where the segment enters and leaves the grid
+        let (mut start, mut end) = ((0.0, from), (1.0, to));
+        for axis in 0..2 {
+            let (min, max) = (-1.0, size[axis] + 1.0);
+            if delta[axis] == 0.0 {
+                if from[axis] < min || from[axis] > max {
+                    return Vec::new();
+                }
+                continue;
+            }
+            let (near, far) = if delta[axis] > 0.0 {
+                (min, max)
+            } else {
+                (max, min)
+            };
+            let enter = (near - from[axis]) / delta[axis];
+            let exit = (far - from[axis]) / delta[axis];
+            // The clipped coordinate is set exactly, as it may be imprecise on long segments
+            if enter > start.0 {
+                start = (enter, at(enter));
+                start.1[axis] = near;
+            }
+            if exit < end.0 {
+                end = (exit, at(exit));
+                end.1[axis] = far;
+            }
+        }
+        if start.0 > end.0 {
+            return Vec::new();
+        }
+        let (start, end) = (start.1, end.1);
+        let (mut x, mut y) = (floor(start[0]), floor(start[1]));
+        let (sx, sy) = (signum(delta[0]), signum(delta[1]));
+        // The parameter at which the segment leaves the current cell along an axis
+        let crossing = |cell: i64, step: i64, axis: usize| {
+            if step == 0 {
+                f32::INFINITY
+            } else {
+                let boundary = if step > 0 { cell + 1 } else { cell };
+                (i64_as_f32(boundary) - from[axis]) / delta[axis]
+            }
+        };
+        let steps = (floor(end[0]) - x).abs() + (floor(end[1]) - y).abs();
+        let mut cells = Vec::new();
+        let mut push = |x: i64, y: i64| {
+            if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) {
+                if self.get(x, y).is_some() {
+                    cells.push((x, y));
+                }
+            }
+        };
+        push(x, y);
+        let mut taken = 0;
+        while taken < steps {
+            let (max_x, max_y) = (crossing(x, sx, 0), crossing(y, sy, 1));
+            if max_x < max_y {
+                x += sx;
+                taken += 1;
+            } else if max_y < max_x {
+                y += sy;
+                taken += 1;
+            } else {
+                push(x + sx, y);
+                push(x, y + sy);
+                x += sx;
+                y += sy;
+                taken += 2;
+            }
+            push(x, y);
+        }
+        cells
+    }
+
+    /// Returns the index of the cell at the given signed coordinate, if in bounds
+    pub(crate) fn cell_index(&self, (x, y): (i64, i64)) -> Option<usize> {
+        let x = usize::try_from(x).ok()?;
+        let y = usize::try_from(y).ok()?;
+        self.index(x, y).filter(|i| *i < self.cells.len())
+    }
+}
+
+/// Returns the cells of Bresenham's line which are in a grid of `size` cells
+///
+/// On the major axis, the line takes one step per cell, and its position on the minor axis is rounded.
+fn bresenham(from: (i128, i128), to: (i128, i128), size: (i128, i128)) -> Vec<(usize, usize)> {
+    let x_major = (to.0 - from.0).abs() >= (to.1 - from.1).abs();
+    // Swap the axes, so that the major axis is the first one
+    let swap = |(a, b): (i128, i128)| if x_major { (a, b) } else { (b, a) };
+    let (from, to, size) = (swap(from), swap(to), swap(size));
+    let (major, minor) = (to.0 - from.0, to.1 - from.1);
+    let (steps, minor_steps) = (major.abs(), minor.abs());
+    let (first, last) = match steps_in_grid(from.0, major.signum(), steps, size.0) {
+        Some(range) => range,
+        None => return Vec::new(),
+    };
+    (first..=last)
+        .filter_map(|step| {
+            let offset = if steps == 0 {
+                0
+            } else {
+                rounded_ratio(step, minor_steps, steps)
+            };
+            let (x, y) = swap((
+                from.0 + major.signum() * step,
+                from.1 + minor.signum() * offset,
+            ));
+            in_grid(x, y, swap(size))
+        })
+        .collect()
+}
+
+/// Returns the cells of the supercover line which are in a grid of `size` cells
+///
+/// The line goes through the centers of the cells `from` and `to`.
+/// When it passes exactly through a corner, both cells sharing the corner are included.
+fn supercover(from: (i128, i128), to: (i128, i128), size: (i128, i128)) -> Vec<(usize, usize)> {
+    let (nx, ny) = ((to.0 - from.0).abs(), (to.1 - from.1).abs());
+    let (sx, sy) = ((to.0 - from.0).signum(), (to.1 - from.1).signum());
+    let (x_range, y_range) = match (
+        steps_in_grid(from.0, sx, nx, size.0),
+        steps_in_grid(from.1, sy, ny, size.1),
+    ) {
+        (Some(x_range), Some(y_range)) => (x_range, y_range),
+        _ => return Vec::new(),
+    };
+    // Jump to the step just before the line enters the grid, on the axis where it enters last
+    let (mut ix, mut iy) = (0, 0);
+    if x_range.0 > 0 {
+        ix = x_range.0 - 1;
+        iy = first_step(ny, |iy| step_order(ix, iy, nx, ny) != Ordering::Greater);
+    }
+    if y_range.0 > 0 {
+        let jy = y_range.0 - 1;
+        let jx = first_step(nx, |jx| step_order(jx, jy, nx, ny) != Ordering::Less);
+        if jx >= ix && jy >= iy {
+            (ix, iy) = (jx, jy);
+        }
+    }
+    let mut cells = Vec::new();
+    let mut push = |ix: i128, iy: i128| {
+        if (x_range.0..=x_range.1).contains(&ix) && (y_range.0..=y_range.1).contains(&iy) {
+            cells.extend(in_grid(from.0 + sx * ix, from.1 + sy * iy, size));
+        }
+    };
+    push(ix, iy);
+    while (ix < nx || iy < ny) && ix <= x_range.1 && iy <= y_range.1 {
+        match step_order(ix, iy, nx, ny) {
+            Ordering::Equal => {
+                push(ix + 1, iy);
+                push(ix, iy + 1);
+                ix += 1;
+                iy += 1;
+            }
+            Ordering::Less => ix += 1,
+            Ordering::Greater => iy += 1,
+        }
+        push(ix, iy);
+    }
+    cells
+}
+
+/// Returns the first and last steps (between `0` and `steps`) where `start + sign * step` is between `0` and `size` (excluded)
+fn steps_in_grid(start: i128, sign: i128, steps: i128, size: i128) -> Option<(i128, i128)> {
+    let (first, last) = match sign.cmp(&0) {
+        Ordering::Equal => (
+            0,
+            if (0..size).contains(&start) {
+                steps
+            } else {
+                -1
+            },
+        ),
+        Ordering::Greater => (-start, size - 1 - start),
+        Ordering::Less => (start - (size - 1), start),
+    };
+    let (first, last) = (first.max(0), last.min(steps));
+    (first <= last).then(|| (first, last))
+}
+
+/// Compares the positions along the supercover line of the next vertical and horizontal cell boundaries
+///
+/// That is `(1 + 2 * ix) * ny` compared to `(1 + 2 * iy) * nx`, without overflowing.
+fn step_order(ix: i128, iy: i128, nx: i128, ny: i128) -> Ordering {
+    let a = ix.unsigned_abs() * ny.unsigned_abs();
+    let b = iy.unsigned_abs() * nx.unsigned_abs();
+    let (nx, ny) = (nx.unsigned_abs(), ny.unsigned_abs());
+    if a >= b {
+        (a - b)
+            .checked_mul(2)
+            .and_then(|difference| difference.checked_add(ny))
+            .map_or(Ordering::Greater, |left| left.cmp(&nx))
+    } else {
+        (b - a)
+            .checked_mul(2)
+            .and_then(|difference| difference.checked_add(nx))
+            .map_or(Ordering::Less, |right| ny.cmp(&right))
+    }
+}
+
+/// Returns the first step between `0` and `last` for which `predicate` is true, assuming it stays true afterwards
+fn first_step(last: i128, mut predicate: impl FnMut(i128) -> bool) -> i128 {
+    let (mut low, mut high) = (0, last);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if predicate(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    low
+}
+
+/// Returns `value * numerator / denominator` rounded to the nearest integer (halves are rounded up), without overflowing
+///
+/// `value` and `numerator` must be between `0` and `denominator`, which must be positive.
+fn rounded_ratio(value: i128, numerator: i128, denominator: i128) -> i128 {
+    let product = value.unsigned_abs() * numerator.unsigned_abs();
+    let denominator_abs = denominator.unsigned_abs();
+    let (quotient, remainder) = (product / denominator_abs, product % denominator_abs);
+    let rounded = quotient + u128::from(2 * remainder >= denominator_abs);
+    // The result is at most `numerator`
+    i128::try_from(rounded).unwrap_or(numerator)
+}
+
+fn in_grid(x: i128, y: i128, (width, height): (i128, i128)) -> Option<(usize, usize)> {
+    if x >= width || y >= height {
+        return None;
+    }
+    Some((usize::try_from(x).ok()?, usize::try_from(y).ok()?))
+}
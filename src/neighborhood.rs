@@ -0,0 +1,52 @@
+/// Set of cells considered adjacent to a given cell
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Neighborhood {
+    /// The 4 orthogonally adjacent cells
+    VonNeumann,
+    /// The 8 orthogonally and diagonally adjacent cells
+    Moore,
+}
+
+const OFFSETS: [(i8, i8); 8] = [
+    (0, -1),
+    (-1, 0),
+    (1, 0),
+    (0, 1),
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+    (1, 1),
+];
+
+impl Neighborhood {
+    /// Returns the offsets of the neighbors relative to the cell
+    pub(crate) fn offsets(self) -> &'static [(i8, i8)] {
+        match self {
+            Self::VonNeumann => &OFFSETS[..4],
+            Self::Moore => &OFFSETS,
+        }
+    }
+
+    /// Returns the in-bounds neighbors of the cell at col `x` and row `y`
+    pub(crate) fn neighbors(
+        self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets()
+            .iter()
+            .filter_map(move |&(dx, dy)| Some((offset(x, dx, width)?, offset(y, dy, height)?)))
+    }
+}
+
+/// Returns `value + delta`, if it is in bounds (`delta` must be -1, 0 or 1)
+pub(crate) fn offset(value: usize, delta: i8, max: usize) -> Option<usize> {
+    match delta {
+        -1 => value.checked_sub(1),
+        0 => Some(value),
+        _ => Some(value + 1).filter(|v| *v < max),
+    }
+}
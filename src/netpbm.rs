@@ -0,0 +1,306 @@
+use core::fmt::Display;
+
+use alloc::{format, vec::Vec};
+
+use crate::DynamicGrid;
+
+/// Error returned when reading an invalid or unsupported Netpbm image
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ParseNetpbmError;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Format {
+    Bitmap,
+    Graymap,
+    Pixmap,
+}
+
+impl DynamicGrid<bool> {
+    /// Read a grid from a PBM image (plain or binary), where black pixels are `true`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseNetpbmError`] if the image is not a valid PBM image
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::from_pbm(b"P1\n# comment\n3 2\n1 0 0\n0 1 1\n").unwrap();
+    /// assert_eq!(grid, DynamicGrid::new_from_iter(3, [true, false, false, false, true, true]).unwrap());
+    /// assert_eq!(DynamicGrid::from_pbm(&grid.to_pbm()).unwrap(), grid);
+    /// ```
+    pub fn from_pbm(bytes: &[u8]) -> Result<Self, ParseNetpbmError> {
+        let (width, _, samples) = decode(bytes, Format::Bitmap, 1)?;
+        Ok(Self {
+            cells: samples.into_iter().map(|s| s == 1).collect(),
+            width,
+        })
+    }
+
+    /// Write the grid as a binary PBM image, where `true` cells are black pixels
+    #[must_use]
+    pub fn to_pbm(&self) -> Vec<u8> {
+        let mut bytes = header("P4", self.width, self.height_or_zero(), None);
+        for row in self.cells.chunks(self.width.max(1)) {
+            for pixels in row.chunks(8) {
+                let byte = pixels
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, black)| **black)
+                    .fold(0_u8, |byte, (i, _)| byte | (0x80 >> i));
+                bytes.push(byte);
+            }
+        }
+        bytes
+    }
+}
+
+impl DynamicGrid<u8> {
+    /// Read a grid from a PGM image (plain or binary), with a maximum value up to `255`
+    ///
+    /// The values are not scaled: a grid read from an image with a maximum value of `15` contains values from `0` to `15`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseNetpbmError`] if the image is not a valid PGM image, or if its maximum value is greater than `255`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::<u8>::from_pgm(b"P2 2 2 255 0 64 128 255").unwrap();
+    /// assert_eq!(grid.get(1, 1), Some(&255));
+    /// assert_eq!(DynamicGrid::<u8>::from_pgm(&grid.to_pgm()).unwrap(), grid);
+    /// ```
+    pub fn from_pgm(bytes: &[u8]) -> Result<Self, ParseNetpbmError> {
+        let (width, _, samples) = decode(bytes, Format::Graymap, 255)?;
+        let cells = samples
+            .into_iter()
+            .map(|s| u8::try_from(s).map_err(|_| ParseNetpbmError))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { cells, width })
+    }
+
+    /// Write the grid as a binary PGM image, with a maximum value of `255`
+    #[must_use]
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut bytes = header("P5", self.width, self.height_or_zero(), Some(255));
+        bytes.extend_from_slice(&self.cells);
+        bytes
+    }
+}
+
+impl DynamicGrid<u16> {
+    /// Read a grid from a PGM image (plain or binary)
+    ///
+    /// The values are not scaled: a grid read from an image with a maximum value of `1023` contains values from `0` to `1023`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseNetpbmError`] if the image is not a valid PGM image
+    pub fn from_pgm(bytes: &[u8]) -> Result<Self, ParseNetpbmError> {
+        let (width, _, cells) = decode(bytes, Format::Graymap, 65535)?;
+        Ok(Self { cells, width })
+    }
+
+    /// Write the grid as a binary PGM image, with a maximum value of `65535`
+    #[must_use]
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut bytes = header("P5", self.width, self.height_or_zero(), Some(65535));
+        for cell in &self.cells {
+            bytes.extend_from_slice(&cell.to_be_bytes());
+        }
+        bytes
+    }
+}
+
+impl DynamicGrid<[u8; 3]> {
+    /// Read a grid of RGB colors from a PPM image (plain or binary), with a maximum value up to `255`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseNetpbmError`] if the image is not a valid PPM image, or if its maximum value is greater than `255`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::from_ppm(b"P3 2 1 255  255 0 0  0 0 255").unwrap();
+    /// assert_eq!(grid.get(1, 0), Some(&[0, 0, 255]));
+    /// assert_eq!(DynamicGrid::from_ppm(&grid.to_ppm()).unwrap(), grid);
+    /// ```
+    pub fn from_ppm(bytes: &[u8]) -> Result<Self, ParseNetpbmError> {
+        let (width, _, samples) = decode(bytes, Format::Pixmap, 255)?;
+        let cells = samples
+            .chunks(3)
+            .map(|rgb| {
+                let mut color = [0; 3];
+                for (channel, sample) in color.iter_mut().zip(rgb) {
+                    *channel = u8::try_from(*sample).map_err(|_| ParseNetpbmError)?;
+                }
+                Ok(color)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { cells, width })
+    }
+
+    /// Write the grid as a binary PPM image, with a maximum value of `255`
+    #[must_use]
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = header("P6", self.width, self.height_or_zero(), Some(255));
+        for cell in &self.cells {
+            bytes.extend_from_slice(cell);
+        }
+        bytes
+    }
+}
+
+fn header(magic: &str, width: usize, height: usize, max_value: Option<u16>) -> Vec<u8> {
+    let header = match max_value {
+        Some(max_value) => format!("{magic}\n{width} {height}\n{max_value}\n"),
+        None => format!("{magic}\n{width} {height}\n"),
+    };
+    header.into_bytes()
+}
+
+/// Decode the image, and returns its width, height and samples
+///
+/// Images whose maximum value is greater than `max_supported` are rejected.
+fn decode(
+    bytes: &[u8],
+    format: Format,
+    max_supported: u16,
+) -> Result<(usize, usize, Vec<u16>), ParseNetpbmError> {
+    let mut reader = Reader { bytes, position: 0 };
+    let (expected, plain) = match reader.bytes.get(..2) {
+        Some(b"P1") => (Format::Bitmap, true),
+        Some(b"P2") => (Format::Graymap, true),
+        Some(b"P3") => (Format::Pixmap, true),
+        Some(b"P4") => (Format::Bitmap, false),
+        Some(b"P5") => (Format::Graymap, false),
+        Some(b"P6") => (Format::Pixmap, false),
+        _ => return Err(ParseNetpbmError),
+    };
+    if expected != format {
+        return Err(ParseNetpbmError);
+    }
+    reader.position = 2;
+    let width: usize = reader.number()?;
+    let height: usize = reader.number()?;
+    let max_value: usize = if format == Format::Bitmap {
+        1
+    } else {
+        reader.number()?
+    };
+    if max_value == 0 || max_value > usize::from(max_supported) {
+        return Err(ParseNetpbmError);
+    }
+    let channels = if format == Format::Pixmap { 3 } else { 1 };
+    let count = width
+        .checked_mul(height)
+        .and_then(|pixels| pixels.checked_mul(channels))
+        .ok_or(ParseNetpbmError)?;
+    let mut samples = Vec::with_capacity(count.min(bytes.len() * 8));
+    if plain {
+        for _ in 0..count {
+            let sample = if format == Format::Bitmap {
+                reader.bit()?
+            } else {
+                reader.number()?
+            };
+            samples.push(sample);
+        }
+    } else {
+        // A single whitespace character separates the header from the raster
+        match reader.bytes.get(reader.position) {
+            Some(separator) if separator.is_ascii_whitespace() => reader.position += 1,
+            _ => return Err(ParseNetpbmError),
+        }
+        let raster = reader
+            .bytes
+            .get(reader.position..)
+            .ok_or(ParseNetpbmError)?;
+        if format == Format::Bitmap {
+            // Rows are padded to a whole number of bytes
+            let row_size = width / 8 + usize::from(width % 8 != 0);
+            let size = row_size.checked_mul(height).ok_or(ParseNetpbmError)?;
+            let raster = raster.get(..size).ok_or(ParseNetpbmError)?;
+            for row in raster.chunks(row_size.max(1)).take(height) {
+                samples.extend((0..width).map(|x| u16::from(row[x / 8] >> (7 - x % 8) & 1)));
+            }
+        } else if max_value < 256 {
+            let raster = raster.get(..count).ok_or(ParseNetpbmError)?;
+            samples.extend(raster.iter().map(|s| u16::from(*s)));
+        } else {
+            let size = count.checked_mul(2).ok_or(ParseNetpbmError)?;
+            let raster = raster.get(..size).ok_or(ParseNetpbmError)?;
+            samples.extend(raster.chunks(2).map(|s| u16::from_be_bytes([s[0], s[1]])));
+        }
+    }
+    if samples.iter().any(|s| usize::from(*s) > max_value) {
+        return Err(ParseNetpbmError);
+    }
+    Ok((width, height, samples))
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Reader<'_> {
+    /// Skip whitespaces and comments
+    fn skip(&mut self) {
+        while let Some(byte) = self.bytes.get(self.position) {
+            match byte {
+                b'#' => {
+                    while !matches!(self.bytes.get(self.position), None | Some(b'\n' | b'\r')) {
+                        self.position += 1;
+                    }
+                }
+                b if b.is_ascii_whitespace() => self.position += 1,
+                _ => break,
+            }
+        }
+    }
+
+    fn number<N: TryFrom<usize>>(&mut self) -> Result<N, ParseNetpbmError> {
+        self.skip();
+        let start = self.position;
+        let mut value: usize = 0;
+        while let Some(digit) = self.bytes.get(self.position).filter(|b| b.is_ascii_digit()) {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(usize::from(digit - b'0')))
+                .ok_or(ParseNetpbmError)?;
+            self.position += 1;
+        }
+        if self.position == start {
+            return Err(ParseNetpbmError);
+        }
+        N::try_from(value).map_err(|_| ParseNetpbmError)
+    }
+
+    /// Read a single `0` or `1` (which may not be separated by whitespace in plain PBM)
+    fn bit(&mut self) -> Result<u16, ParseNetpbmError> {
+        self.skip();
+        let bit = match self.bytes.get(self.position) {
+            Some(b'0') => 0,
+            Some(b'1') => 1,
+            _ => return Err(ParseNetpbmError),
+        };
+        self.position += 1;
+        Ok(bit)
+    }
+}
+
+impl Display for ParseNetpbmError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Invalid or unsupported netpbm image")
+    }
+}
+
+#[rustversion::since(1.81)]
+impl core::error::Error for ParseNetpbmError {}
@@ -0,0 +1,79 @@
+//! Numeric conversions shared by the algorithms of the crate
+//!
+//! Integer conversions saturate instead of failing,
+//! and float helpers are provided here because `core` does not implement them on every supported Rust version.
+
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn as_f32(value: usize) -> f32 {
+    value as f32
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn as_f64(value: usize) -> f64 {
+    value as f64
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn i64_as_f32(value: i64) -> f32 {
+    value as f32
+}
+
+pub(crate) fn to_isize(value: usize) -> isize {
+    isize::try_from(value).unwrap_or(isize::MAX)
+}
+
+pub(crate) fn to_i64(value: usize) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+pub(crate) fn to_i128(value: usize) -> i128 {
+    i128::try_from(value).unwrap_or(i128::MAX)
+}
+
+pub(crate) fn to_u64(value: usize) -> u64 {
+    u64::try_from(value).unwrap_or(u64::MAX)
+}
+
+/// Truncate a non-negative value
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn truncate(value: f64) -> usize {
+    value as usize
+}
+
+/// Round a value between `0.0` and `255.0`
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn round_to_u8(value: f64) -> u8 {
+    (value + 0.5) as u8
+}
+
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn floor(value: f32) -> i64 {
+    let truncated = value as i64;
+    if i64_as_f32(truncated) > value {
+        truncated.saturating_sub(1)
+    } else {
+        truncated
+    }
+}
+
+pub(crate) fn ceil(value: f32) -> i64 {
+    floor(-value).saturating_neg()
+}
+
+pub(crate) fn signum(value: f32) -> i64 {
+    if value > 0.0 {
+        1
+    } else if value < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+pub(crate) fn abs(value: f32) -> f32 {
+    if value < 0.0 {
+        -value
+    } else {
+        value
+    }
+}
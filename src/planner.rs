@@ -0,0 +1,275 @@
+use core::cmp::{Ordering, Reverse};
+
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+
+use crate::{Cost, DynamicGrid, Neighborhood};
+
+type Key<C> = (C, C);
+
+/// Incremental path planner on a [`DynamicGrid`], based on D* Lite
+///
+/// The planner searches backward from the goal, and keeps its search state between calls.
+/// After reporting changed cells with [`Self::update`] and moves of the start with [`Self::set_start`],
+/// the next call to [`Self::path`] only repairs the part of the search that was affected.
+///
+/// # Example
+///
+/// ```
+/// # use cell_grid::{DynamicGrid, Neighborhood, PathPlanner};
+/// let mut grid = DynamicGrid::new_with(3, 3, |_, _| true);
+/// let walkable = |cell: &bool| cell.then(|| 1_u32);
+/// let heuristic = |dx: usize, dy: usize| (dx + dy) as u32;
+/// let mut planner = PathPlanner::new(&grid, (0, 0), (2, 0), Neighborhood::VonNeumann, heuristic, walkable);
+/// assert_eq!(planner.path().unwrap().len(), 3);
+///
+/// grid.set(1, 0, false);
+/// planner.update(&grid, 1, 0, 1, 1, walkable);
+/// assert_eq!(planner.path().unwrap().len(), 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathPlanner<C> {
+    width: usize,
+    height: usize,
+    neighborhood: Neighborhood,
+    heuristic: fn(usize, usize) -> C,
+    costs: Vec<Option<C>>,
+    g: Vec<Option<C>>,
+    rhs: Vec<Option<C>>,
+    open: Vec<Option<Key<C>>>,
+    heap: BinaryHeap<Reverse<(Key<C>, usize)>>,
+    start: usize,
+    goal: usize,
+    key_modifier: C,
+}
+
+impl<C: Cost> PathPlanner<C> {
+    /// Create a planner from `start` to `goal` over the `grid`
+    ///
+    /// The `cost` function returns the cost of moving into a cell, or `None` if the cell cannot be entered.
+    ///
+    /// The `heuristic` receives the horizontal and vertical distances between two cells and must never overestimate
+    /// the cost of moving between them. Use a function returning [`Cost::ZERO`] if no such estimate is available.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` or `goal` is out of bounds
+    #[must_use]
+    pub fn new<T>(
+        grid: &DynamicGrid<T>,
+        start: (usize, usize),
+        goal: (usize, usize),
+        neighborhood: Neighborhood,
+        heuristic: fn(usize, usize) -> C,
+        cost: impl FnMut(&T) -> Option<C>,
+    ) -> Self {
+        let start = grid
+            .index(start.0, start.1)
+            .filter(|i| *i < grid.cells.len())
+            .expect("start is out-of-bounds");
+        let goal = grid
+            .index(goal.0, goal.1)
+            .filter(|i| *i < grid.cells.len())
+            .expect("goal is out-of-bounds");
+        let len = grid.cells.len();
+        let mut planner = Self {
+            width: grid.width,
+            height: grid.height(),
+            neighborhood,
+            heuristic,
+            costs: grid.cells.iter().map(cost).collect(),
+            g: vec![None; len],
+            rhs: vec![None; len],
+            open: vec![None; len],
+            heap: BinaryHeap::new(),
+            start,
+            goal,
+            key_modifier: C::ZERO,
+        };
+        planner.restart();
+        planner
+    }
+
+    /// Returns the current start
+    #[must_use]
+    pub fn start(&self) -> (usize, usize) {
+        DynamicGrid::<()>::index_to_coord(self.start, self.width)
+    }
+
+    /// Returns the goal
+    #[must_use]
+    pub fn goal(&self) -> (usize, usize) {
+        DynamicGrid::<()>::index_to_coord(self.goal, self.width)
+    }
+
+    /// Move the start, typically after the agent moved along the path
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is out of bounds
+    pub fn set_start(&mut self, start: (usize, usize)) {
+        assert!(
+            start.0 < self.width && start.1 < self.height,
+            "start is out-of-bounds"
+        );
+        let (x, y) = self.start();
+        let moved = (self.heuristic)(x.abs_diff(start.0), y.abs_diff(start.1));
+        self.start = start.1 * self.width + start.0;
+        match self.key_modifier.checked_add(moved) {
+            Some(key_modifier) => self.key_modifier = key_modifier,
+            // The keys of the previous search cannot be adjusted anymore
+            None => self.restart(),
+        }
+    }
+
+    /// Report that the cells in the rectangle at col `x`, row `y` of size `width` and `height` have changed
+    ///
+    /// The `cost` function must be the same as the one given when creating the planner.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the `grid` differs from the one given when creating the planner
+    pub fn update<T>(
+        &mut self,
+        grid: &DynamicGrid<T>,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        mut cost: impl FnMut(&T) -> Option<C>,
+    ) {
+        assert!(
+            grid.width == self.width && grid.cells.len() == self.costs.len(),
+            "the grid size does not match the planner size"
+        );
+        let mut changed = Vec::new();
+        for y in y..y.saturating_add(height).min(self.height) {
+            for x in x..x.saturating_add(width).min(self.width) {
+                let index = y * self.width + x;
+                let new_cost = cost(&grid.cells[index]);
+                if new_cost != self.costs[index] {
+                    self.costs[index] = new_cost;
+                    changed.push((x, y));
+                }
+            }
+        }
+        for (x, y) in changed {
+            for (nx, ny) in self.neighbors(x, y) {
+                self.update_vertex(ny * self.width + nx);
+            }
+        }
+    }
+
+    /// Returns the cheapest path from the start to the goal (both included)
+    ///
+    /// Returns `None` if the goal cannot be reached.
+    pub fn path(&mut self) -> Option<Vec<(usize, usize)>> {
+        self.compute_shortest_path();
+        self.g[self.start]?;
+        let mut current = self.start;
+        let mut path = vec![self.start()];
+        while current != self.goal {
+            let (x, y) = DynamicGrid::<()>::index_to_coord(current, self.width);
+            current = self
+                .neighbors(x, y)
+                .map(|(x, y)| y * self.width + x)
+                .filter_map(|n| Some((self.through(n)?, n)))
+                .min()?
+                .1;
+            path.push(DynamicGrid::<()>::index_to_coord(current, self.width));
+            if path.len() > self.costs.len() {
+                return None;
+            }
+        }
+        Some(path)
+    }
+
+    fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighborhood.neighbors(x, y, self.width, self.height)
+    }
+
+    /// Forget the search state, so that the next search starts from scratch
+    fn restart(&mut self) {
+        self.g.fill(None);
+        self.rhs.fill(None);
+        self.open.fill(None);
+        self.heap.clear();
+        self.key_modifier = C::ZERO;
+        self.rhs[self.goal] = Some(C::ZERO);
+        self.update_vertex(self.goal);
+    }
+
+    /// Cost of reaching the goal by first moving into `index`, or `None` if it overflows
+    fn through(&self, index: usize) -> Option<C> {
+        self.costs[index]?.checked_add(self.g[index]?)
+    }
+
+    fn key(&self, index: usize) -> Option<Key<C>> {
+        let value = match (self.g[index], self.rhs[index]) {
+            (Some(g), Some(rhs)) => g.min(rhs),
+            (Some(v), None) | (None, Some(v)) => v,
+            (None, None) => return None,
+        };
+        let (x, y) = DynamicGrid::<()>::index_to_coord(index, self.width);
+        let (sx, sy) = self.start();
+        let heuristic = (self.heuristic)(x.abs_diff(sx), y.abs_diff(sy));
+        let priority = value
+            .checked_add(heuristic)?
+            .checked_add(self.key_modifier)?;
+        Some((priority, value))
+    }
+
+    fn update_vertex(&mut self, index: usize) {
+        if index != self.goal {
+            let (x, y) = DynamicGrid::<()>::index_to_coord(index, self.width);
+            self.rhs[index] = self
+                .neighbors(x, y)
+                .filter_map(|(x, y)| self.through(y * self.width + x))
+                .min();
+        }
+        self.open[index] = if self.g[index] == self.rhs[index] {
+            None
+        } else {
+            self.key(index)
+        };
+        if let Some(key) = self.open[index] {
+            self.heap.push(Reverse((key, index)));
+        }
+    }
+
+    fn top(&mut self) -> Option<(Key<C>, usize)> {
+        while let Some(&Reverse((key, index))) = self.heap.peek() {
+            if self.open[index] == Some(key) {
+                return Some((key, index));
+            }
+            self.heap.pop();
+        }
+        None
+    }
+
+    fn compute_shortest_path(&mut self) {
+        while let Some((old_key, index)) = self.top() {
+            let start_key = self.key(self.start);
+            let before_start = start_key.map_or(true, |k| old_key.cmp(&k) == Ordering::Less);
+            if !before_start && self.g[self.start] == self.rhs[self.start] {
+                break;
+            }
+            let new_key = self.key(index);
+            let (x, y) = DynamicGrid::<()>::index_to_coord(index, self.width);
+            if new_key.map_or(false, |k| old_key < k) {
+                self.update_vertex(index);
+            } else if self.rhs[index].map_or(false, |rhs| self.g[index].map_or(true, |g| g > rhs)) {
+                self.g[index] = self.rhs[index];
+                self.open[index] = None;
+                for (nx, ny) in self.neighbors(x, y).collect::<Vec<_>>() {
+                    self.update_vertex(ny * self.width + nx);
+                }
+            } else {
+                self.g[index] = None;
+                self.update_vertex(index);
+                for (nx, ny) in self.neighbors(x, y).collect::<Vec<_>>() {
+                    self.update_vertex(ny * self.width + nx);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use core::fmt::Display;
+use std::io;
+
+use crate::DynamicGrid;
+
+/// Error returned when reading or writing a PNG image
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct PngError {
+    kind: PngErrorKind,
+}
+
+#[derive(Debug)]
+enum PngErrorKind {
+    Decoding(png::DecodingError),
+    Encoding(png::EncodingError),
+    TooLarge,
+}
+
+impl<T> DynamicGrid<T> {
+    /// Read a grid from a PNG image, mapping the color of each pixel to a cell with `palette`
+    ///
+    /// Colors are given in RGBA, with 8 bits per channel, whatever the format of the image.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PngError`] if the image cannot be decoded
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_from_iter(2, "#..#".chars()).unwrap();
+    /// let mut png = Vec::new();
+    /// grid.to_png(&mut png, |c| if *c == '#' { [0, 0, 0, 255] } else { [255, 255, 255, 255] }).unwrap();
+    /// let read = DynamicGrid::from_png(png.as_slice(), |color| if color == [0, 0, 0, 255] { '#' } else { '.' }).unwrap();
+    /// assert_eq!(read, grid);
+    /// ```
+    pub fn from_png(
+        reader: impl io::Read,
+        mut palette: impl FnMut([u8; 4]) -> T,
+    ) -> Result<Self, PngError> {
+        let mut decoder = png::Decoder::new(reader);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().map_err(PngError::decoding)?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer).map_err(PngError::decoding)?;
+        let width = usize::try_from(info.width).map_err(|_| PngError::too_large())?;
+        let height = usize::try_from(info.height).map_err(|_| PngError::too_large())?;
+        let channels = info.color_type.samples();
+        let mut cells = Vec::with_capacity(width * height);
+        for row in buffer.chunks(info.line_size).take(height) {
+            for pixel in row.chunks(channels).take(width) {
+                let color = match *pixel {
+                    [gray] => [gray, gray, gray, 255],
+                    [gray, alpha] => [gray, gray, gray, alpha],
+                    [r, g, b] => [r, g, b, 255],
+                    [r, g, b, a] => [r, g, b, a],
+                    _ => unreachable!("8-bit pixels have from 1 to 4 channels"),
+                };
+                cells.push(palette(color));
+            }
+        }
+        Ok(Self { cells, width })
+    }
+
+    /// Write the grid as an RGBA PNG image, with one pixel per cell, colored by `color`
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PngError`] if the image cannot be encoded or written
+    pub fn to_png(
+        &self,
+        writer: impl io::Write,
+        mut color: impl FnMut(&T) -> [u8; 4],
+    ) -> Result<(), PngError> {
+        let width = u32::try_from(self.width).map_err(|_| PngError::too_large())?;
+        let height = u32::try_from(self.height_or_zero()).map_err(|_| PngError::too_large())?;
+        let mut encoder = png::Encoder::new(writer, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(PngError::encoding)?;
+        let data: Vec<u8> = self.cells.iter().flat_map(&mut color).collect();
+        writer.write_image_data(&data).map_err(PngError::encoding)?;
+        writer.finish().map_err(PngError::encoding)
+    }
+}
+
+impl PngError {
+    fn decoding(error: png::DecodingError) -> Self {
+        Self {
+            kind: PngErrorKind::Decoding(error),
+        }
+    }
+
+    fn encoding(error: png::EncodingError) -> Self {
+        Self {
+            kind: PngErrorKind::Encoding(error),
+        }
+    }
+
+    fn too_large() -> Self {
+        Self {
+            kind: PngErrorKind::TooLarge,
+        }
+    }
+}
+
+impl Display for PngError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.kind {
+            PngErrorKind::Decoding(error) => write!(f, "Cannot decode PNG image: {error}"),
+            PngErrorKind::Encoding(error) => write!(f, "Cannot encode PNG image: {error}"),
+            PngErrorKind::TooLarge => write!(f, "The image is too large"),
+        }
+    }
+}
+
+impl std::error::Error for PngError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            PngErrorKind::Decoding(error) => Some(error),
+            PngErrorKind::Encoding(error) => Some(error),
+            PngErrorKind::TooLarge => None,
+        }
+    }
+}
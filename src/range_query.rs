@@ -0,0 +1,300 @@
+use core::ops::{Add, Sub};
+
+use alloc::vec::Vec;
+
+use crate::DynamicGrid;
+
+/// Two-dimensional Fenwick tree, to update cells and sum the cells in any rectangle in logarithmic time
+///
+/// See [`DynamicGrid::fenwick_tree`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FenwickTree<S> {
+    values: Vec<S>,
+    tree: Vec<S>,
+    width: usize,
+    height: usize,
+}
+
+/// Two-dimensional segment tree, to update cells and aggregate the cells in any rectangle in logarithmic time
+///
+/// See [`DynamicGrid::segment_tree`]
+#[derive(Debug, Clone)]
+pub struct SegmentTree<T> {
+    nodes: Vec<Option<T>>,
+    width: usize,
+    height: usize,
+    combine: fn(T, T) -> T,
+}
+
+impl<T> DynamicGrid<T> {
+    /// Build a [`FenwickTree`] of the cells
+    ///
+    /// Unlike a [`SummedAreaTable`](crate::SummedAreaTable), it can be updated when cells change.
+    /// The cells are converted to the sum type `S`, which may be wider than `T` to avoid overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, FenwickTree};
+    /// let grid = DynamicGrid::new_from_iter(3, [1_u8, 2, 3, 4, 5, 6]).unwrap();
+    /// let mut tree: FenwickTree<u32> = grid.fenwick_tree();
+    /// assert_eq!(tree.sum_in_rect(1, 0, 2, 2), 2 + 3 + 5 + 6);
+    /// tree.set(2, 1, 10);
+    /// assert_eq!(tree.sum_in_rect(1, 0, 2, 2), 2 + 3 + 5 + 10);
+    /// ```
+    #[must_use]
+    pub fn fenwick_tree<S>(&self) -> FenwickTree<S>
+    where
+        T: Copy + Into<S>,
+        S: Copy + Default + Add<Output = S> + Sub<Output = S>,
+    {
+        let width = self.width;
+        let height = self.height_or_zero();
+        let values: Vec<S> = self.cells.iter().map(|cell| (*cell).into()).collect();
+        let mut tree = values.clone();
+        for y in 0..height {
+            for x in 0..width {
+                let parent = x | (x + 1);
+                if parent < width {
+                    tree[y * width + parent] = tree[y * width + parent] + tree[y * width + x];
+                }
+            }
+        }
+        for y in 0..height {
+            let parent = y | (y + 1);
+            if parent < height {
+                for x in 0..width {
+                    tree[parent * width + x] = tree[parent * width + x] + tree[y * width + x];
+                }
+            }
+        }
+        FenwickTree {
+            values,
+            tree,
+            width,
+            height,
+        }
+    }
+
+    /// Build a [`SegmentTree`] of the cells, aggregated with the `combine` function
+    ///
+    /// The `combine` function must be associative and commutative, such as [`Ord::min`] or [`Ord::max`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_from_iter(3, [5, 2, 7, 4, 9, 1]).unwrap();
+    /// let mut tree = grid.segment_tree(Ord::max);
+    /// assert_eq!(tree.query_rect(0, 0, 2, 2), Some(9));
+    /// tree.set(1, 1, 0);
+    /// assert_eq!(tree.query_rect(0, 0, 2, 2), Some(5));
+    /// ```
+    #[must_use]
+    pub fn segment_tree(&self, combine: fn(T, T) -> T) -> SegmentTree<T>
+    where
+        T: Copy,
+    {
+        let width = self.width;
+        let height = self.height_or_zero();
+        let stride = 2 * width;
+        let mut nodes = Vec::new();
+        nodes.resize(stride * 2 * height, None);
+        let mut tree = SegmentTree {
+            nodes,
+            width,
+            height,
+            combine,
+        };
+        for (index, cell) in self.cells.iter().enumerate() {
+            let (x, y) = Self::index_to_coord(index, width);
+            tree.nodes[(height + y) * stride + width + x] = Some(*cell);
+        }
+        for row in height..2 * height {
+            for x in (1..width).rev() {
+                tree.nodes[row * stride + x] = tree.merge(
+                    tree.nodes[row * stride + 2 * x],
+                    tree.nodes[row * stride + 2 * x + 1],
+                );
+            }
+        }
+        for row in (1..height).rev() {
+            for x in 1..stride {
+                tree.nodes[row * stride + x] = tree.merge(
+                    tree.nodes[2 * row * stride + x],
+                    tree.nodes[(2 * row + 1) * stride + x],
+                );
+            }
+        }
+        tree
+    }
+}
+
+impl<S> FenwickTree<S>
+where
+    S: Copy + Default + Add<Output = S> + Sub<Output = S>,
+{
+    /// Returns the value of the cell at col `x` and row `y`
+    ///
+    /// Returns `None` if `x` and `y` are out of bounds
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Option<S> {
+        (x < self.width && y < self.height).then(|| self.values[y * self.width + x])
+    }
+
+    /// Add `delta` to the cell at col `x` and row `y` and return the new value
+    ///
+    /// Returns `None` if `x` and `y` are out of bounds
+    pub fn add(&mut self, x: usize, y: usize, delta: S) -> Option<S> {
+        let old = self.get(x, y)?;
+        self.set(x, y, old + delta);
+        Some(old + delta)
+    }
+
+    /// Set the new value to the cell at col `x` and row `y` and return the old value
+    ///
+    /// Returns `None` if `x` and `y` are out of bounds
+    pub fn set(&mut self, x: usize, y: usize, value: S) -> Option<S> {
+        let old = self.get(x, y)?;
+        self.values[y * self.width + x] = value;
+        let mut row = y;
+        while row < self.height {
+            let mut col = x;
+            while col < self.width {
+                let node = &mut self.tree[row * self.width + col];
+                *node = *node - old + value;
+                col |= col + 1;
+            }
+            row |= row + 1;
+        }
+        Some(old)
+    }
+
+    /// Returns the sum of the cells in the rectangle, in logarithmic time
+    ///
+    /// The rectangle is clipped to the grid, like [`DynamicGrid::cells_in_rect`].
+    #[must_use]
+    pub fn sum_in_rect(&self, x: usize, y: usize, width: usize, height: usize) -> S {
+        let x0 = x.min(self.width);
+        let y0 = y.min(self.height);
+        let x1 = x.saturating_add(width).min(self.width);
+        let y1 = y.saturating_add(height).min(self.height);
+        (self.prefix_sum(x1, y1) - self.prefix_sum(x1, y0))
+            - (self.prefix_sum(x0, y1) - self.prefix_sum(x0, y0))
+    }
+
+    /// Sum of the cells in the rectangle from `(0, 0)` (inclusive) to `(x, y)` (exclusive)
+    fn prefix_sum(&self, x: usize, y: usize) -> S {
+        let mut sum = S::default();
+        let mut row = y;
+        while row > 0 {
+            let mut col = x;
+            while col > 0 {
+                sum = sum + self.tree[(row - 1) * self.width + col - 1];
+                col &= col - 1;
+            }
+            row &= row - 1;
+        }
+        sum
+    }
+}
+
+impl<T: Copy> SegmentTree<T> {
+    /// Returns the value of the cell at col `x` and row `y`
+    ///
+    /// Returns `None` if `x` and `y` are out of bounds
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Option<T> {
+        if x < self.width && y < self.height {
+            self.nodes[(self.height + y) * 2 * self.width + self.width + x]
+        } else {
+            None
+        }
+    }
+
+    /// Set the new value to the cell at col `x` and row `y` and return the old value
+    ///
+    /// Returns `None` if `x` and `y` are out of bounds
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> Option<T> {
+        let old = self.get(x, y)?;
+        let stride = 2 * self.width;
+        let mut row = self.height + y;
+        let leaf = self.width + x;
+        self.nodes[row * stride + leaf] = Some(value);
+        let mut col = leaf / 2;
+        while col > 0 {
+            self.nodes[row * stride + col] = self.merge(
+                self.nodes[row * stride + 2 * col],
+                self.nodes[row * stride + 2 * col + 1],
+            );
+            col /= 2;
+        }
+        row /= 2;
+        while row > 0 {
+            let mut col = leaf;
+            while col > 0 {
+                self.nodes[row * stride + col] = self.merge(
+                    self.nodes[2 * row * stride + col],
+                    self.nodes[(2 * row + 1) * stride + col],
+                );
+                col /= 2;
+            }
+            row /= 2;
+        }
+        Some(old)
+    }
+
+    /// Returns the aggregate of the cells in the rectangle, in logarithmic time
+    ///
+    /// The rectangle is clipped to the grid, like [`DynamicGrid::cells_in_rect`].
+    /// Returns `None` if the clipped rectangle is empty.
+    #[must_use]
+    pub fn query_rect(&self, x: usize, y: usize, width: usize, height: usize) -> Option<T> {
+        let x0 = x.min(self.width);
+        let x1 = x.saturating_add(width).min(self.width);
+        let mut low = y.min(self.height) + self.height;
+        let mut high = y.saturating_add(height).min(self.height) + self.height;
+        let mut result = None;
+        while low < high {
+            if low % 2 == 1 {
+                result = self.merge(result, self.query_row(low, x0, x1));
+                low += 1;
+            }
+            if high % 2 == 1 {
+                high -= 1;
+                result = self.merge(result, self.query_row(high, x0, x1));
+            }
+            low /= 2;
+            high /= 2;
+        }
+        result
+    }
+
+    fn query_row(&self, row: usize, x0: usize, x1: usize) -> Option<T> {
+        let offset = row * 2 * self.width;
+        let mut low = x0 + self.width;
+        let mut high = x1 + self.width;
+        let mut result = None;
+        while low < high {
+            if low % 2 == 1 {
+                result = self.merge(result, self.nodes[offset + low]);
+                low += 1;
+            }
+            if high % 2 == 1 {
+                high -= 1;
+                result = self.merge(result, self.nodes[offset + high]);
+            }
+            low /= 2;
+            high /= 2;
+        }
+        result
+    }
+
+    fn merge(&self, a: Option<T>, b: Option<T>) -> Option<T> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some((self.combine)(a, b)),
+            (a, None) => a,
+            (None, b) => b,
+        }
+    }
+}
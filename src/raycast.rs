@@ -0,0 +1,112 @@
+use crate::{
+    numeric::{abs, floor, i64_as_f32, signum},
+    DynamicGrid,
+};
+
+/// Result of a successful [`DynamicGrid::raycast`]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct RayHit {
+    /// Coordinate (col, row) of the hit cell
+    pub cell: (usize, usize),
+    /// Distance along the ray, in multiple of the length of the ray direction
+    pub distance: f32,
+    /// Point (in world space) where the ray enters the hit cell
+    pub point: [f32; 2],
+    /// Normal of the face of the hit cell that was crossed by the ray
+    ///
+    /// It is `[0, 0]` if the ray starts inside the hit cell.
+    pub normal: [i32; 2],
+}
+
+impl<T> DynamicGrid<T> {
+    /// Cast a ray and returns the first cell for which `hit` returns `true`
+    ///
+    /// The ray starts at `origin` and goes toward `direction`, up to `max_distance`.
+    /// Positions are in world space, where the cell at col `x` and row `y` covers the area
+    /// from `[x * cell_size[0], y * cell_size[1]]` to `[(x + 1) * cell_size[0], (y + 1) * cell_size[1]]`.
+    ///
+    /// Distances are measured in multiple of the length of `direction`.
+    /// Hence, if `direction` is normalized, the distances are in world units.
+    ///
+    /// Returns `None` if nothing is hit within `max_distance`, or if the ray leaves the grid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_from_iter(4, "...#".chars()).unwrap();
+    /// let hit = grid.raycast([2.0, 0.5], [1.0, 0.0], 100.0, [2.0, 1.0], |c| *c == '#').unwrap();
+    /// assert_eq!(hit.cell, (3, 0));
+    /// assert_eq!(hit.distance, 4.0);
+    /// assert_eq!(hit.point, [6.0, 0.5]);
+    /// assert_eq!(hit.normal, [-1, 0]);
+    /// ```
+    #[must_use]
+    pub fn raycast(
+        &self,
+        origin: [f32; 2],
+        direction: [f32; 2],
+        max_distance: f32,
+        cell_size: [f32; 2],
+        mut hit: impl FnMut(&T) -> bool,
+    ) -> Option<RayHit> {
+        let all_finite = origin
+            .iter()
+            .chain(&direction)
+            .chain(&cell_size)
+            .all(|v| v.is_finite());
+        if !all_finite || direction == [0.0, 0.0] || cell_size.iter().any(|s| *s <= 0.0) {
+            return None;
+        }
+        let start = [origin[0] / cell_size[0], origin[1] / cell_size[1]];
+        let delta = [direction[0] / cell_size[0], direction[1] / cell_size[1]];
+        let step = [signum(delta[0]), signum(delta[1])];
+        let mut cell = [floor(start[0]), floor(start[1])];
+        let t_delta = [1.0 / abs(delta[0]), 1.0 / abs(delta[1])];
+        let mut t_max = [0.0; 2];
+        for axis in 0..2 {
+            t_max[axis] = match step[axis] {
+                0 => f32::INFINITY,
+                1 => (i64_as_f32(cell[axis] + 1) - start[axis]) / delta[axis],
+                _ => (i64_as_f32(cell[axis]) - start[axis]) / delta[axis],
+            };
+        }
+        let size = [
+            i64::try_from(self.width).ok()?,
+            i64::try_from(self.height_or_zero()).ok()?,
+        ];
+        let mut distance = 0.0;
+        let mut normal = [0, 0];
+        loop {
+            if let Some(index) = self.cell_index((cell[0], cell[1])) {
+                if hit(&self.cells[index]) {
+                    return Some(RayHit {
+                        cell: Self::index_to_coord(index, self.width),
+                        distance,
+                        point: [
+                            origin[0] + direction[0] * distance,
+                            origin[1] + direction[1] * distance,
+                        ],
+                        normal,
+                    });
+                }
+            }
+            let leaving = (0..2).any(|axis| {
+                (cell[axis] < 0 && step[axis] <= 0) || (cell[axis] >= size[axis] && step[axis] >= 0)
+            });
+            if leaving {
+                return None;
+            }
+            let axis = usize::from(t_max[1] < t_max[0]);
+            distance = t_max[axis];
+            if distance > max_distance {
+                return None;
+            }
+            cell[axis] += step[axis];
+            t_max[axis] += t_delta[axis];
+            normal = [0, 0];
+            normal[axis] = if step[axis] > 0 { -1 } else { 1 };
+        }
+    }
+}
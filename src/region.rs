@@ -0,0 +1,376 @@
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+use crate::{CellRect, DynamicGrid, Neighborhood};
+
+/// Connected regions of a grid, returned by [`DynamicGrid::regions`]
+///
+/// Regions are identified by indices from `0` to `count() - 1`, in the row-major order of their first cell.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Regions {
+    labels: DynamicGrid<Option<usize>>,
+    sizes: Vec<usize>,
+    neighborhood: Neighborhood,
+}
+
+/// Two regions which touch each other, returned by [`Regions::adjacency`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct RegionAdjacency {
+    /// The two regions, the lowest index first
+    pub regions: (usize, usize),
+    /// Pairs of adjacent cells (col, row) through which the regions touch, the cell of the first region first
+    pub border: Vec<((usize, usize), (usize, usize))>,
+}
+
+impl<T> DynamicGrid<T> {
+    /// Label the connected regions of the grid
+    ///
+    /// Adjacent cells (according to the `neighborhood`) belong to the same region if `key` returns the same value for both.
+    /// Cells for which `key` returns `None` (like walls) do not belong to any region.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, Neighborhood};
+    /// let grid = DynamicGrid::new_from_iter(4, "aa#bab#b".chars()).unwrap();
+    /// let regions = grid.regions(Neighborhood::VonNeumann, |c| (*c != '#').then(|| *c));
+    /// assert_eq!(regions.count(), 3);
+    /// assert_eq!(regions.region(0, 1), regions.region(1, 0));
+    /// assert_eq!(regions.region(2, 0), None);
+    /// ```
+    #[must_use]
+    pub fn regions<K: Eq>(
+        &self,
+        neighborhood: Neighborhood,
+        key: impl FnMut(&T) -> Option<K>,
+    ) -> Regions {
+        let width = self.width;
+        let height = self.height_or_zero();
+        let keys: Vec<Option<K>> = self.cells.iter().map(key).collect();
+        let mut labels = vec![None; keys.len()];
+        let mut sizes = Vec::new();
+        let mut queue = VecDeque::new();
+        for start in 0..keys.len() {
+            if labels[start].is_some() || keys[start].is_none() {
+                continue;
+            }
+            let label = sizes.len();
+            let mut size = 0;
+            labels[start] = Some(label);
+            queue.push_back(start);
+            while let Some(index) = queue.pop_front() {
+                size += 1;
+                let (x, y) = Self::index_to_coord(index, width);
+                for (nx, ny) in neighborhood.neighbors(x, y, width, height) {
+                    let neighbor = ny * width + nx;
+                    if labels[neighbor].is_none() && keys[neighbor] == keys[start] {
+                        labels[neighbor] = Some(label);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+        Regions {
+            labels: DynamicGrid {
+                cells: labels,
+                width,
+            },
+            sizes,
+            neighborhood,
+        }
+    }
+
+    /// Returns the walkable cells which would disconnect their region if they were not walkable
+    ///
+    /// The cells are returned in row-major order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, Neighborhood};
+    /// let grid = DynamicGrid::new_from_iter(3, "..##..".chars()).unwrap();
+    /// let cells = grid.articulation_cells(Neighborhood::VonNeumann, |c| *c == '.');
+    /// assert_eq!(cells, [(1, 0), (1, 1)]);
+    /// ```
+    #[must_use]
+    pub fn articulation_cells(
+        &self,
+        neighborhood: Neighborhood,
+        walkable: impl FnMut(&T) -> bool,
+    ) -> Vec<(usize, usize)> {
+        let width = self.width;
+        let height = self.height_or_zero();
+        let walkable: Vec<bool> = self.cells.iter().map(walkable).collect();
+        let articulation = articulation_points(
+            walkable.len(),
+            |index| walkable[index],
+            |index| {
+                let (x, y) = Self::index_to_coord(index, width);
+                neighborhood
+                    .neighbors(x, y, width, height)
+                    .map(move |(x, y)| y * width + x)
+                    .filter(|i| walkable[*i])
+            },
+        );
+        articulation
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| **a)
+            .map(|(index, _)| Self::index_to_coord(index, width))
+            .collect()
+    }
+
+    /// Returns the narrow passages which would disconnect their region if they were not walkable
+    ///
+    /// A passage is a horizontal or vertical line of at most `max_width` walkable cells,
+    /// bounded on both ends by non-walkable cells (or the edges of the grid).
+    /// Passages of width `1` are also [articulation cells](Self::articulation_cells).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{CellRect, DynamicGrid, Neighborhood};
+    /// let grid = DynamicGrid::new_from_iter(5, "..#..............#..".chars()).unwrap();
+    /// let passages = grid.chokepoints(Neighborhood::VonNeumann, 2, |c| *c == '.');
+    /// assert_eq!(passages, [CellRect::new(2, 1, 1, 2)]);
+    /// ```
+    #[must_use]
+    pub fn chokepoints(
+        &self,
+        neighborhood: Neighborhood,
+        max_width: usize,
+        walkable: impl FnMut(&T) -> bool,
+    ) -> Vec<CellRect> {
+        let width = self.width;
+        let height = self.height_or_zero();
+        let walkable = DynamicGrid {
+            cells: self.cells.iter().map(walkable).collect::<Vec<bool>>(),
+            width,
+        };
+        let mut chokepoints = Vec::new();
+        for horizontal in [true, false] {
+            let (stride, lines, length) = if horizontal {
+                (1, height, width)
+            } else {
+                (width, width, height)
+            };
+            let line_start = |line: usize| if horizontal { line * width } else { line };
+            // Each passage is contracted into its first cell, which is an articulation point of the
+            // contracted graph if and only if the passage disconnects its region.
+            // Passages of the same orientation do not overlap, so they can all be contracted at once.
+            let mut representative: Vec<usize> = (0..walkable.cells.len()).collect();
+            let mut passages = Vec::new();
+            for line in 0..lines {
+                let first = line_start(line);
+                runs(
+                    length,
+                    |i| walkable.cells[first + i * stride],
+                    |i, run| {
+                        if run <= max_width {
+                            let start = first + i * stride;
+                            for cell in 0..run {
+                                representative[start + cell * stride] = start;
+                            }
+                            passages.push((start, run));
+                        }
+                    },
+                );
+            }
+            let mut lengths = vec![1; walkable.cells.len()];
+            for &(start, run) in &passages {
+                lengths[start] = run;
+            }
+            let articulation = articulation_points(
+                walkable.cells.len(),
+                |index| walkable.cells[index] && representative[index] == index,
+                |index| {
+                    let representative = &representative;
+                    (0..lengths[index])
+                        .flat_map(move |cell| {
+                            let (x, y) = Self::index_to_coord(index + cell * stride, width);
+                            neighborhood.neighbors(x, y, width, height)
+                        })
+                        .map(move |(x, y)| y * width + x)
+                        .filter(|i| walkable.cells[*i])
+                        .map(move |i| representative[i])
+                        .filter(move |i| *i != index)
+                },
+            );
+            chokepoints.extend(
+                passages
+                    .into_iter()
+                    .filter(|(start, _)| articulation[*start])
+                    .map(|(start, run)| {
+                        let (x, y) = Self::index_to_coord(start, width);
+                        if horizontal {
+                            CellRect::new(x, y, run, 1)
+                        } else {
+                            CellRect::new(x, y, 1, run)
+                        }
+                    }),
+            );
+        }
+        chokepoints.sort_by_key(|rect| (rect.y, rect.x, rect.height));
+        chokepoints.dedup();
+        chokepoints
+    }
+}
+
+impl Regions {
+    /// Returns the number of regions
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    /// Returns the region of the cell at col `x` and row `y`
+    ///
+    /// Returns `None` if the cell is out of bounds, or does not belong to any region
+    #[must_use]
+    pub fn region(&self, x: usize, y: usize) -> Option<usize> {
+        self.labels.get(x, y).copied().flatten()
+    }
+
+    /// Returns the number of cells in the `region`
+    ///
+    /// Returns `None` if the region does not exist
+    #[must_use]
+    pub fn size(&self, region: usize) -> Option<usize> {
+        self.sizes.get(region).copied()
+    }
+
+    /// Returns the grid of the region of each cell
+    #[must_use]
+    pub fn labels(&self) -> &DynamicGrid<Option<usize>> {
+        &self.labels
+    }
+
+    /// Returns the region adjacency graph: the pairs of regions which touch each other, and the cells through which they touch
+    ///
+    /// The pairs are sorted by region indices.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, Neighborhood};
+    /// let grid = DynamicGrid::new_from_iter(3, "aab".chars()).unwrap();
+    /// let regions = grid.regions(Neighborhood::VonNeumann, |c| Some(*c));
+    /// let adjacency = regions.adjacency();
+    /// assert_eq!(adjacency[0].regions, (0, 1));
+    /// assert_eq!(adjacency[0].border, [((1, 0), (2, 0))]);
+    /// ```
+    #[must_use]
+    pub fn adjacency(&self) -> Vec<RegionAdjacency> {
+        let width = self.labels.width;
+        let height = self.labels.height_or_zero();
+        let mut borders = Vec::new();
+        for ((x, y), label) in self.labels.cells_with_coords() {
+            let label = match label {
+                Some(label) => *label,
+                None => continue,
+            };
+            for (nx, ny) in self.neighborhood.neighbors(x, y, width, height) {
+                match self.labels.cells[ny * width + nx] {
+                    Some(other) if label < other => {
+                        borders.push(((label, other), ((x, y), (nx, ny))));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        borders.sort_by_key(|(regions, _)| *regions);
+        let mut adjacency: Vec<RegionAdjacency> = Vec::new();
+        for (regions, cells) in borders {
+            match adjacency.last_mut() {
+                Some(last) if last.regions == regions => last.border.push(cells),
+                _ => adjacency.push(RegionAdjacency {
+                    regions,
+                    border: vec![cells],
+                }),
+            }
+        }
+        adjacency
+    }
+}
+
+/// Call `found` with the start and length of each maximal run of cells matching `predicate`
+fn runs(length: usize, predicate: impl Fn(usize) -> bool, mut found: impl FnMut(usize, usize)) {
+    let mut start = 0;
+    while start < length {
+        let run = (start..length).take_while(|i| predicate(*i)).count();
+        if run > 0 {
+            found(start, run);
+        }
+        start += run + 1;
+    }
+}
+
+/// Returns whether each node of a graph is an articulation point (Tarjan's algorithm)
+///
+/// Only the nodes for which `is_node` returns `true` are part of the graph,
+/// and `neighbors` must only return such nodes.
+fn articulation_points<I: Iterator<Item = usize>>(
+    node_count: usize,
+    is_node: impl Fn(usize) -> bool,
+    neighbors: impl Fn(usize) -> I,
+) -> Vec<bool> {
+    struct Frame<I> {
+        index: usize,
+        parent: Option<usize>,
+        neighbors: I,
+        children: usize,
+    }
+    let mut discovery = vec![0; node_count];
+    let mut low = vec![0; node_count];
+    let mut articulation = vec![false; node_count];
+    let mut time = 0;
+    let mut stack: Vec<Frame<I>> = Vec::new();
+    for root in 0..node_count {
+        if !is_node(root) || discovery[root] != 0 {
+            continue;
+        }
+        time += 1;
+        discovery[root] = time;
+        low[root] = time;
+        stack.push(Frame {
+            index: root,
+            parent: None,
+            neighbors: neighbors(root),
+            children: 0,
+        });
+        while let Some(frame) = stack.last_mut() {
+            let current = frame.index;
+            if let Some(neighbor) = frame.neighbors.next() {
+                if discovery[neighbor] == 0 {
+                    frame.children += 1;
+                    time += 1;
+                    discovery[neighbor] = time;
+                    low[neighbor] = time;
+                    stack.push(Frame {
+                        index: neighbor,
+                        parent: Some(current),
+                        neighbors: neighbors(neighbor),
+                        children: 0,
+                    });
+                } else if frame.parent != Some(neighbor) {
+                    low[current] = low[current].min(discovery[neighbor]);
+                }
+                continue;
+            }
+            let children = frame.children;
+            let parent = frame.parent;
+            stack.pop();
+            match parent {
+                Some(parent) => {
+                    low[parent] = low[parent].min(low[current]);
+                    if parent != root && low[current] >= discovery[parent] {
+                        articulation[parent] = true;
+                    }
+                }
+                None => articulation[current] = children >= 2,
+            }
+        }
+    }
+    articulation
+}
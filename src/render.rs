@@ -0,0 +1,279 @@
+use core::fmt::{self, Display, Formatter, Write};
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{numeric::as_f32, DynamicGrid};
+
+/// Style of a cell, used by [`DynamicGrid::to_svg`] and [`DynamicGrid::to_html_table`]
+///
+/// Colors are CSS colors, such as `"red"` or `"#ff8800"`.
+///
+/// # Example
+///
+/// ```
+/// # use cell_grid::CellStyle;
+/// let style = CellStyle::new().fill("#224").stroke("white").label(42);
+/// assert_eq!(style.label.as_deref(), Some("42"));
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CellStyle {
+    /// Background color of the cell, or `None` to leave it transparent
+    pub fill: Option<String>,
+    /// Color of the border of the cell, or `None` for no border
+    pub stroke: Option<String>,
+    /// Text written at the center of the cell
+    pub label: Option<String>,
+}
+
+/// SVG rendering of a grid, returned by [`DynamicGrid::to_svg`]
+pub struct SvgDisplay<'a, T, F> {
+    grid: &'a DynamicGrid<T>,
+    cell_style: F,
+    cell_size: f32,
+    grid_lines: Option<String>,
+    paths: Vec<(Vec<(usize, usize)>, String)>,
+}
+
+impl CellStyle {
+    /// Create a style without fill, stroke, or label
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the background color
+    #[must_use]
+    pub fn fill(mut self, color: impl Into<String>) -> Self {
+        self.fill = Some(color.into());
+        self
+    }
+
+    /// Set the border color
+    #[must_use]
+    pub fn stroke(mut self, color: impl Into<String>) -> Self {
+        self.stroke = Some(color.into());
+        self
+    }
+
+    /// Set the label
+    #[must_use]
+    pub fn label(mut self, label: impl Display) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+}
+
+impl<T> DynamicGrid<T> {
+    /// Returns a value implementing [`Display`], which renders the grid as an SVG image
+    ///
+    /// Each cell is drawn as a square, styled by `cell_style`.
+    /// Grid lines and paths (such as the ones found by a [`PathPlanner`](crate::PathPlanner)) can be drawn over the cells.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{CellStyle, DynamicGrid};
+    /// let grid = DynamicGrid::new_from_iter(3, "..#...".chars()).unwrap();
+    /// let svg = grid
+    ///     .to_svg(|c| if *c == '#' { CellStyle::new().fill("black") } else { CellStyle::new() })
+    ///     .cell_size(10.0)
+    ///     .grid_lines("gray")
+    ///     .path([(0, 0), (1, 1), (2, 1)], "red")
+    ///     .to_string();
+    /// assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="20""#));
+    /// assert!(svg.contains(r#"<rect x="20" y="0" width="10" height="10" fill="black"/>"#));
+    /// assert!(svg.contains(r#"<polyline points="5,5 15,15 25,15""#));
+    /// ```
+    pub fn to_svg<F>(&self, cell_style: F) -> SvgDisplay<'_, T, F>
+    where
+        F: Fn(&T) -> CellStyle,
+    {
+        SvgDisplay {
+            grid: self,
+            cell_style,
+            cell_size: 20.0,
+            grid_lines: None,
+            paths: Vec::new(),
+        }
+    }
+
+    /// Render the grid as a self-contained HTML table, with inline styles
+    ///
+    /// Each cell is a `<td>` styled by `cell_style`, and containing its label.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{CellStyle, DynamicGrid};
+    /// let grid = DynamicGrid::new_from_iter(2, [1, 2]).unwrap();
+    /// let html = grid.to_html_table(|c| CellStyle::new().label(c));
+    /// assert!(html.contains("<tr><td style=\"width:20px;height:20px;text-align:center\">1</td>"));
+    /// ```
+    #[must_use]
+    pub fn to_html_table(&self, cell_style: impl Fn(&T) -> CellStyle) -> String {
+        let mut html =
+            String::from("<table style=\"border-collapse:collapse;font-family:monospace\">\n");
+        for row in self.cells.chunks(self.width.max(1)) {
+            html.push_str("<tr>");
+            for cell in row {
+                let style = cell_style(cell);
+                html.push_str("<td style=\"width:20px;height:20px;text-align:center");
+                if let Some(fill) = &style.fill {
+                    html.push_str(";background:");
+                    html.push_str(&escape(fill));
+                }
+                if let Some(stroke) = &style.stroke {
+                    html.push_str(";border:1px solid ");
+                    html.push_str(&escape(stroke));
+                }
+                html.push_str("\">");
+                if let Some(label) = &style.label {
+                    html.push_str(&escape(label));
+                }
+                html.push_str("</td>");
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+        html
+    }
+}
+
+impl<T, F> SvgDisplay<'_, T, F>
+where
+    F: Fn(&T) -> CellStyle,
+{
+    /// Set the size of a cell, in pixels (`20` by default)
+    #[must_use]
+    pub fn cell_size(mut self, size: f32) -> Self {
+        self.cell_size = size;
+        self
+    }
+
+    /// Draw the lines between the cells, with the given color
+    #[must_use]
+    pub fn grid_lines(mut self, color: impl Into<String>) -> Self {
+        self.grid_lines = Some(color.into());
+        self
+    }
+
+    /// Draw a path through the centers of the given cells, with the given color
+    ///
+    /// Can be called multiple times to draw multiple paths.
+    #[must_use]
+    pub fn path(
+        mut self,
+        cells: impl IntoIterator<Item = (usize, usize)>,
+        color: impl Into<String>,
+    ) -> Self {
+        self.paths.push((cells.into_iter().collect(), color.into()));
+        self
+    }
+}
+
+impl<T, F> Display for SvgDisplay<'_, T, F>
+where
+    F: Fn(&T) -> CellStyle,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let size = self.cell_size;
+        let width = as_f32(self.grid.width) * size;
+        let height = as_f32(self.grid.height_or_zero()) * size;
+        writeln!(
+            f,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">"
+        )?;
+        for (y, row) in self.grid.cells.chunks(self.grid.width.max(1)).enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                let style = (self.cell_style)(cell);
+                let (left, top) = (as_f32(x) * size, as_f32(y) * size);
+                if style.fill.is_some() || style.stroke.is_some() {
+                    write!(
+                        f,
+                        "<rect x=\"{left}\" y=\"{top}\" width=\"{size}\" height=\"{size}\" fill=\"{}\"",
+                        style.fill.as_deref().map_or_else(|| "none".into(), escape)
+                    )?;
+                    if let Some(stroke) = &style.stroke {
+                        write!(f, " stroke=\"{}\"", escape(stroke))?;
+                    }
+                    f.write_str("/>\n")?;
+                }
+                if let Some(label) = &style.label {
+                    writeln!(
+                        f,
+                        "<text x=\"{}\" y=\"{}\" font-size=\"{}\" font-family=\"monospace\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>",
+                        left + size / 2.0,
+                        top + size / 2.0,
+                        size * 0.6,
+                        escape(label)
+                    )?;
+                }
+            }
+        }
+        if let Some(color) = &self.grid_lines {
+            f.write_str("<path d=\"")?;
+            for x in 0..=self.grid.width {
+                write!(f, "M{} 0V{height}", as_f32(x) * size)?;
+            }
+            for y in 0..=self.grid.height_or_zero() {
+                write!(f, "M0 {}H{width}", as_f32(y) * size)?;
+            }
+            writeln!(
+                f,
+                "\" fill=\"none\" stroke=\"{}\" stroke-width=\"1\"/>",
+                escape(color)
+            )?;
+        }
+        for (cells, color) in &self.paths {
+            f.write_str("<polyline points=\"")?;
+            for (index, &(x, y)) in cells.iter().enumerate() {
+                if index > 0 {
+                    f.write_char(' ')?;
+                }
+                write!(
+                    f,
+                    "{},{}",
+                    (as_f32(x) + 0.5) * size,
+                    (as_f32(y) + 0.5) * size
+                )?;
+            }
+            writeln!(
+                f,
+                "\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" stroke-linecap=\"round\" stroke-linejoin=\"round\"/>",
+                escape(color),
+                size / 8.0
+            )?;
+        }
+        f.write_str("</svg>\n")
+    }
+}
+
+impl<T, F> fmt::Debug for SvgDisplay<'_, T, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SvgDisplay")
+            .field("cell_size", &self.cell_size)
+            .field("grid_lines", &self.grid_lines)
+            .field("paths", &self.paths)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Escape the characters which have a special meaning in XML and HTML
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,65 @@
+//! Serialize a [`DynamicGrid`] as a list of rows, instead of `{ width, height, cells }`
+//!
+//! To be used with `#[serde(with = "cell_grid::serde_rows")]`.
+//! The width of an empty grid is not preserved.
+//!
+//! # Example
+//!
+//! ```
+//! # use cell_grid::DynamicGrid;
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize)]
+//! struct Level {
+//!     #[serde(with = "cell_grid::serde_rows")]
+//!     tiles: DynamicGrid<u8>,
+//! }
+//!
+//! let level = Level { tiles: DynamicGrid::new_from_iter(2, [1, 2, 3, 4]).unwrap() };
+//! let json = serde_json::to_string(&level).unwrap();
+//! assert_eq!(json, r#"{"tiles":[[1,2],[3,4]]}"#);
+//! let level: Level = serde_json::from_str(&json).unwrap();
+//! assert_eq!(level.tiles.get(1, 1), Some(&4));
+//! ```
+
+use alloc::vec::Vec;
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::DynamicGrid;
+
+/// Serialize the `grid` as a list of rows
+///
+/// # Errors
+///
+/// Returns an error if the serializer fails
+pub fn serialize<T, S>(grid: &DynamicGrid<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    serializer.collect_seq(grid.cells.chunks(grid.width.max(1)))
+}
+
+/// Deserialize a grid from a list of rows
+///
+/// # Errors
+///
+/// Returns an error if the deserializer fails, or if the rows do not all have the same length
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<DynamicGrid<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let rows: Vec<Vec<T>> = Vec::deserialize(deserializer)?;
+    let mut grid = DynamicGrid::new();
+    for (index, row) in rows.into_iter().enumerate() {
+        let len = row.len();
+        let width = grid.width;
+        grid.push_row(row).map_err(|_| {
+            D::Error::custom(format_args!(
+                "row {index} has {len} cells, but the previous rows have {width}"
+            ))
+        })?;
+    }
+    Ok(grid)
+}
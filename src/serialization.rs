@@ -0,0 +1,52 @@
+use alloc::vec::Vec;
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::DynamicGrid;
+
+#[derive(Serialize)]
+#[serde(rename = "DynamicGrid")]
+struct GridRef<'a, T> {
+    width: usize,
+    height: usize,
+    cells: &'a [T],
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "DynamicGrid")]
+struct GridData<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Serialize> Serialize for DynamicGrid<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GridRef {
+            width: self.width,
+            height: self.height_or_zero(),
+            cells: &self.cells,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for DynamicGrid<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let GridData {
+            width,
+            height,
+            cells,
+        } = GridData::deserialize(deserializer)?;
+        let expected = width
+            .checked_mul(height)
+            .ok_or_else(|| D::Error::custom("grid size overflows"))?;
+        if cells.len() != expected {
+            return Err(D::Error::custom(format_args!(
+                "expected {expected} cells for a grid of {width} by {height}, found {}",
+                cells.len()
+            )));
+        }
+        Ok(Self { cells, width })
+    }
+}
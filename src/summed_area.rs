@@ -0,0 +1,105 @@
+use core::ops::{Add, Mul, Sub};
+
+use alloc::vec::Vec;
+
+use crate::DynamicGrid;
+
+/// Table of prefix sums, to compute the sum of the cells in any rectangle in constant time
+///
+/// See [`DynamicGrid::summed_area_table`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SummedAreaTable<S> {
+    sums: Vec<S>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> DynamicGrid<T> {
+    /// Build a [`SummedAreaTable`] of the cells, which can then sum the cells in any rectangle in constant time
+    ///
+    /// The cells are converted to the sum type `S`, which may be wider than `T` to avoid overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, SummedAreaTable};
+    /// let grid = DynamicGrid::new_from_iter(3, [1_u8, 2, 3, 4, 5, 6, 7, 8, 9]).unwrap();
+    /// let table: SummedAreaTable<u32> = grid.summed_area_table();
+    /// assert_eq!(table.sum_in_rect(1, 1, 2, 2), 5 + 6 + 8 + 9);
+    /// ```
+    #[must_use]
+    pub fn summed_area_table<S>(&self) -> SummedAreaTable<S>
+    where
+        T: Copy + Into<S>,
+        S: Copy + Default + Add<Output = S> + Sub<Output = S>,
+    {
+        SummedAreaTable::build(self, |cell| (*cell).into())
+    }
+
+    /// Build a [`SummedAreaTable`] of the squares of the cells
+    ///
+    /// Together with [`Self::summed_area_table`], it gives the variance of the cells in any rectangle in constant time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::{DynamicGrid, SummedAreaTable};
+    /// let grid = DynamicGrid::new_from_iter(2, [1.0_f32, 3.0, 1.0, 3.0]).unwrap();
+    /// let sums: SummedAreaTable<f64> = grid.summed_area_table();
+    /// let squares: SummedAreaTable<f64> = grid.squared_summed_area_table();
+    /// let mean = sums.sum_in_rect(0, 0, 2, 2) / 4.0;
+    /// let variance = squares.sum_in_rect(0, 0, 2, 2) / 4.0 - mean * mean;
+    /// assert_eq!(variance, 1.0);
+    /// ```
+    #[must_use]
+    pub fn squared_summed_area_table<S>(&self) -> SummedAreaTable<S>
+    where
+        T: Copy + Into<S>,
+        S: Copy + Default + Add<Output = S> + Sub<Output = S> + Mul<Output = S>,
+    {
+        SummedAreaTable::build(self, |cell| {
+            let value: S = (*cell).into();
+            value * value
+        })
+    }
+}
+
+impl<S> SummedAreaTable<S>
+where
+    S: Copy + Default + Add<Output = S> + Sub<Output = S>,
+{
+    fn build<T>(grid: &DynamicGrid<T>, mut value: impl FnMut(&T) -> S) -> Self {
+        let width = grid.width;
+        let height = grid.height_or_zero();
+        let stride = width + 1;
+        let mut sums = Vec::with_capacity(stride * (height + 1));
+        sums.resize(stride, S::default());
+        for row in grid.cells.chunks(width.max(1)).take(height) {
+            let mut row_sum = S::default();
+            sums.push(S::default());
+            for cell in row {
+                row_sum = row_sum + value(cell);
+                let above = sums[sums.len() - stride];
+                sums.push(above + row_sum);
+            }
+        }
+        Self {
+            sums,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the sum of the cells in the rectangle, in constant time
+    ///
+    /// The rectangle is clipped to the grid, like [`DynamicGrid::cells_in_rect`].
+    #[must_use]
+    pub fn sum_in_rect(&self, x: usize, y: usize, width: usize, height: usize) -> S {
+        let x0 = x.min(self.width);
+        let y0 = y.min(self.height);
+        let x1 = x.saturating_add(width).min(self.width);
+        let y1 = y.saturating_add(height).min(self.height);
+        let at = |x: usize, y: usize| self.sums[y * (self.width + 1) + x];
+        (at(x1, y1) - at(x1, y0)) - (at(x0, y1) - at(x0, y0))
+    }
+}
@@ -0,0 +1,220 @@
+//! Rendering of grids in a terminal, with ANSI escape sequences
+//!
+//! * [`DynamicGrid::to_ansi`] renders a grid with a symbol and 24-bit colors per cell
+//! * [`DynamicGrid::to_braille`] renders a grid of `bool` with braille characters, packing 2x4 cells per character
+//! * [`IncrementalRenderer`] only emits the changes between two frames, which is useful for live views
+//!
+//! # Example
+//!
+//! ```
+//! # use cell_grid::{DynamicGrid, terminal::{IncrementalRenderer, TerminalCell}};
+//! let mut grid = DynamicGrid::new_from_iter(2, "#..#".chars()).unwrap();
+//! let cell = |c: &char| TerminalCell::new(*c).foreground([0, 255, 0]);
+//! assert_eq!(grid.to_ansi(cell), "\x1b[0;38;2;0;255;0m#.\x1b[0m\n\x1b[0;38;2;0;255;0m.#\x1b[0m\n");
+//!
+//! let mut renderer = IncrementalRenderer::new();
+//! assert!(renderer.render(&grid, cell).starts_with("\x1b[2J\x1b[1;1H"));
+//! grid.set(1, 0, '@');
+//! assert_eq!(renderer.render(&grid, cell), "\x1b[1;2H\x1b[0;38;2;0;255;0m@\x1b[0m");
+//! assert_eq!(renderer.render(&grid, cell), "");
+//! ```
+
+use core::fmt::Write;
+
+use alloc::string::String;
+
+use crate::DynamicGrid;
+
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Appearance of a cell in the terminal
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct TerminalCell {
+    /// Character displayed in the cell (it should occupy a single column in the terminal)
+    pub symbol: char,
+    /// Color of the symbol, or `None` for the default color of the terminal
+    pub foreground: Option<[u8; 3]>,
+    /// Color of the background, or `None` for the default color of the terminal
+    pub background: Option<[u8; 3]>,
+}
+
+/// Renders successive frames of a grid, emitting only the escape sequences for the cells that changed
+///
+/// The grid is drawn at the top-left corner of the terminal.
+/// The first frame, and any frame whose size differs from the previous one, clears the screen and draws all the cells.
+#[derive(Debug, Clone, Default)]
+pub struct IncrementalRenderer {
+    previous: Option<DynamicGrid<TerminalCell>>,
+}
+
+impl TerminalCell {
+    /// Create a cell displaying `symbol` with the default colors of the terminal
+    #[must_use]
+    pub fn new(symbol: char) -> Self {
+        Self {
+            symbol,
+            foreground: None,
+            background: None,
+        }
+    }
+
+    /// Set the color of the symbol
+    #[must_use]
+    pub fn foreground(mut self, color: [u8; 3]) -> Self {
+        self.foreground = Some(color);
+        self
+    }
+
+    /// Set the color of the background
+    #[must_use]
+    pub fn background(mut self, color: [u8; 3]) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    fn has_style(self) -> bool {
+        self.foreground.is_some() || self.background.is_some()
+    }
+
+    fn same_style(self, other: Self) -> bool {
+        self.foreground == other.foreground && self.background == other.background
+    }
+}
+
+impl IncrementalRenderer {
+    /// Create a renderer which has not rendered any frame yet
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget the previous frame, so that the next one is fully redrawn
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+
+    /// Returns the escape sequences to update the terminal from the previous frame to `grid`
+    ///
+    /// Returns an empty string if no cell changed.
+    pub fn render<T>(
+        &mut self,
+        grid: &DynamicGrid<T>,
+        mut cell: impl FnMut(&T) -> TerminalCell,
+    ) -> String {
+        let frame = DynamicGrid {
+            cells: grid.cells.iter().map(&mut cell).collect(),
+            width: grid.width,
+        };
+        let previous = self.previous.as_ref().filter(|previous| {
+            previous.width == frame.width && previous.cells.len() == frame.cells.len()
+        });
+        let mut output = String::new();
+        if previous.is_none() {
+            output.push_str("\x1b[2J");
+        }
+        let mut current: Option<TerminalCell> = None;
+        let mut cursor: Option<usize> = None;
+        for (index, &new) in frame.cells.iter().enumerate() {
+            if previous.map_or(false, |previous| previous.cells[index] == new) {
+                continue;
+            }
+            if cursor != Some(index) || index % frame.width == 0 {
+                let (x, y) = (index % frame.width, index / frame.width);
+                // Writing to a `String` cannot fail
+                let _ = write!(output, "\x1b[{};{}H", y + 1, x + 1);
+            }
+            push_styled(&mut output, &mut current, new);
+            cursor = Some(index + 1);
+        }
+        if current.map_or(false, TerminalCell::has_style) {
+            output.push_str("\x1b[0m");
+        }
+        self.previous = Some(frame);
+        output
+    }
+}
+
+impl<T> DynamicGrid<T> {
+    /// Render the grid as text with ANSI escape sequences, one line per row
+    ///
+    /// The symbol and colors of each cell are given by `cell`. Colors are reset at the end of each line.
+    ///
+    /// See the [`terminal`](crate::terminal) module for an example
+    #[must_use]
+    pub fn to_ansi(&self, mut cell: impl FnMut(&T) -> TerminalCell) -> String {
+        let mut output = String::new();
+        for row in self.cells.chunks(self.width.max(1)) {
+            let mut current = None;
+            for value in row {
+                push_styled(&mut output, &mut current, cell(value));
+            }
+            if current.map_or(false, TerminalCell::has_style) {
+                output.push_str("\x1b[0m");
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+impl DynamicGrid<bool> {
+    /// Render the grid with braille characters, where each character displays 2 columns and 4 rows of cells
+    ///
+    /// A raised dot is a `true` cell. Rows are separated by new lines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::parse_text("
+    /// o..o
+    /// .o..
+    /// ..o.
+    /// o...
+    /// oo..
+    /// ", |c| c == 'o').unwrap();
+    /// assert_eq!(grid.to_braille(), "⡑⠌\n⠉⠀\n");
+    /// ```
+    #[must_use]
+    pub fn to_braille(&self) -> String {
+        let height = self.height_or_zero();
+        let mut output = String::new();
+        for top in (0..height).step_by(4) {
+            for left in (0..self.width).step_by(2) {
+                let mut code = 0x2800;
+                for (dy, dots) in BRAILLE_DOTS.iter().enumerate() {
+                    for (dx, dot) in dots.iter().enumerate() {
+                        if self.get(left + dx, top + dy) == Some(&true) {
+                            code |= dot;
+                        }
+                    }
+                }
+                output.push(char::from_u32(code).unwrap_or(' '));
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+/// Push the symbol of `cell`, preceded by the escape sequence to change the colors if they differ from `current`
+fn push_styled(output: &mut String, current: &mut Option<TerminalCell>, cell: TerminalCell) {
+    let changed = match *current {
+        Some(current) => !current.same_style(cell),
+        None => cell.has_style(),
+    };
+    if changed {
+        output.push_str("\x1b[0");
+        // Writing to a `String` cannot fail
+        if let Some([r, g, b]) = cell.foreground {
+            let _ = write!(output, ";38;2;{r};{g};{b}");
+        }
+        if let Some([r, g, b]) = cell.background {
+            let _ = write!(output, ";48;2;{r};{g};{b}");
+        }
+        output.push('m');
+    }
+    *current = Some(cell);
+    output.push(cell.symbol);
+}
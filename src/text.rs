@@ -0,0 +1,183 @@
+use core::fmt::{self, Display, Formatter, Write};
+
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{DynamicGrid, IncompatibleRowSize};
+
+/// Text rendering of a grid, returned by [`DynamicGrid::display_with`]
+pub struct TextDisplay<'a, T, F> {
+    grid: &'a DynamicGrid<T>,
+    cell_formatter: F,
+    align_columns: bool,
+    border: bool,
+}
+
+impl<T> DynamicGrid<T> {
+    /// Create a grid from newline-separated lines of text, mapping each character to a cell with `char_mapper`
+    ///
+    /// Empty lines are ignored, which allows surrounding the text with blank lines.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IncompatibleRowSize`] if a line does not have the same number of characters as the previous lines.
+    /// The error contains the [line number](IncompatibleRowSize::line) of the incompatible line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::parse_text("
+    /// #..
+    /// ..#
+    /// ", |c| c == '#').unwrap();
+    /// assert_eq!(grid.width(), 3);
+    /// assert_eq!(grid.get(2, 1), Some(&true));
+    ///
+    /// let error = DynamicGrid::parse_text("#..\n..#\n.#", |c| c).unwrap_err();
+    /// assert_eq!(error.line(), Some(3));
+    /// ```
+    pub fn parse_text(
+        text: &str,
+        mut char_mapper: impl FnMut(char) -> T,
+    ) -> Result<Self, IncompatibleRowSize> {
+        let mut grid = Self::new();
+        for (index, line) in text.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            grid.push_row(line.chars().map(&mut char_mapper))
+                .map_err(|_| IncompatibleRowSize {
+                    line: Some(index + 1),
+                })?;
+        }
+        Ok(grid)
+    }
+
+    /// Returns a value implementing [`Display`], which renders the grid as text, one line per row
+    ///
+    /// Each cell is rendered with the value returned by `cell_formatter`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cell_grid::DynamicGrid;
+    /// let grid = DynamicGrid::new_from_iter(2, [1, 20, 300, 4]).unwrap();
+    /// assert_eq!(grid.display_with(|c| *c).to_string(), "120\n3004");
+    /// assert_eq!(
+    ///     grid.display_with(|c| *c).align_columns().with_border().to_string(),
+    ///     "┌──────┐\n│  1 20│\n│300  4│\n└──────┘",
+    /// );
+    /// ```
+    pub fn display_with<F, D>(&self, cell_formatter: F) -> TextDisplay<'_, T, F>
+    where
+        F: Fn(&T) -> D,
+        D: Display,
+    {
+        TextDisplay {
+            grid: self,
+            cell_formatter,
+            align_columns: false,
+            border: false,
+        }
+    }
+}
+
+impl<T, F, D> TextDisplay<'_, T, F>
+where
+    F: Fn(&T) -> D,
+    D: Display,
+{
+    /// Right-align the cells of each column, and separate the columns with a space
+    #[must_use]
+    pub fn align_columns(mut self) -> Self {
+        self.align_columns = true;
+        self
+    }
+
+    /// Surround the grid with a box-drawing border
+    #[must_use]
+    pub fn with_border(mut self) -> Self {
+        self.border = true;
+        self
+    }
+}
+
+impl<T, F, D> Display for TextDisplay<'_, T, F>
+where
+    F: Fn(&T) -> D,
+    D: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let width = self.grid.width;
+        let cells: Vec<String> = self
+            .grid
+            .cells
+            .iter()
+            .map(|cell| {
+                let mut text = String::new();
+                write!(text, "{}", (self.cell_formatter)(cell)).map(|()| text)
+            })
+            .collect::<Result<_, _>>()?;
+        let mut column_widths = vec![0; width];
+        if self.align_columns {
+            for (index, cell) in cells.iter().enumerate() {
+                let column = &mut column_widths[index % width];
+                *column = (*column).max(cell.chars().count());
+            }
+        }
+        let lines: Vec<String> = cells
+            .chunks(width.max(1))
+            .map(|row| {
+                let mut line = String::new();
+                for (x, cell) in row.iter().enumerate() {
+                    if self.align_columns && x > 0 {
+                        line.push(' ');
+                    }
+                    let padding = column_widths[x].saturating_sub(cell.chars().count());
+                    line.extend(core::iter::repeat(' ').take(padding));
+                    line.push_str(cell);
+                }
+                line
+            })
+            .collect();
+        let line_width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let horizontal = |f: &mut Formatter<'_>, left: char, right: char| {
+            f.write_char(left)?;
+            for _ in 0..line_width {
+                f.write_char('─')?;
+            }
+            f.write_char(right)
+        };
+        if self.border {
+            horizontal(f, '┌', '┐')?;
+            f.write_char('\n')?;
+        }
+        for (y, line) in lines.iter().enumerate() {
+            if y > 0 {
+                f.write_char('\n')?;
+            }
+            if self.border {
+                let padding = line_width - line.chars().count();
+                write!(f, "│{line}{:padding$}│", "")?;
+            } else {
+                f.write_str(line)?;
+            }
+        }
+        if self.border {
+            if !lines.is_empty() {
+                f.write_char('\n')?;
+            }
+            horizontal(f, '└', '┘')?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, F> fmt::Debug for TextDisplay<'_, T, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextDisplay")
+            .field("align_columns", &self.align_columns)
+            .field("border", &self.border)
+            .finish_non_exhaustive()
+    }
+}
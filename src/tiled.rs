@@ -0,0 +1,954 @@
+//! Import and export of [Tiled](https://www.mapeditor.org) maps
+//!
+//! Both the TMX (XML) and JSON formats are supported, for orthogonal maps, including infinite maps.
+//! Layer data can be encoded as CSV, as a list of XML tiles, or in base64, uncompressed or compressed with zlib or gzip.
+//!
+//! Each tile layer becomes a [`DynamicGrid<Option<TileId>>`], where empty cells are `None`.
+//! Tile layers nested in groups are flattened, in order, while the other kinds of layers (objects, images) are ignored.
+//!
+//! # Example
+//!
+//! ```
+//! # use cell_grid::tiled::{DataEncoding, Map, TileId};
+//! let tmx = r#"
+//! <map orientation="orthogonal" width="3" height="2" tilewidth="16" tileheight="16" infinite="0">
+//!   <tileset firstgid="1" source="terrain.tsx"/>
+//!   <layer id="1" name="Ground" width="3" height="2">
+//!     <data encoding="csv">1,2,0,
+//! 3,2147483649,1</data>
+//!   </layer>
+//! </map>
+//! "#;
+//! let map = Map::from_tmx(tmx).unwrap();
+//! let ground = &map.layers[0].tiles;
+//! assert_eq!(ground.get(0, 0).copied().flatten().map(TileId::gid), Some(1));
+//! assert_eq!(ground.get(2, 0), Some(&None));
+//!
+//! let flipped = ground.get(1, 1).unwrap().unwrap();
+//! assert_eq!(flipped.gid(), 1);
+//! assert!(flipped.is_flipped_horizontally());
+//!
+//! let json = map.to_json(DataEncoding::Base64Zlib);
+//! assert_eq!(Map::from_json(&json).unwrap(), map);
+//! ```
+
+use core::{
+    fmt::{Display, Write as _},
+    num::NonZeroU32,
+    str::FromStr,
+};
+use std::io::{self, Read, Write as _};
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use base64::Engine;
+use serde_json::{json, Value};
+
+use crate::{
+    json::{self, InvalidField},
+    render::escape,
+    DynamicGrid,
+};
+
+const FLIPPED_HORIZONTALLY: u32 = 0x8000_0000;
+const FLIPPED_VERTICALLY: u32 = 0x4000_0000;
+const FLIPPED_DIAGONALLY: u32 = 0x2000_0000;
+const ROTATED_HEXAGONAL_120: u32 = 0x1000_0000;
+const FLAGS: u32 =
+    FLIPPED_HORIZONTALLY | FLIPPED_VERTICALLY | FLIPPED_DIAGONALLY | ROTATED_HEXAGONAL_120;
+
+/// Maximum number of tiles of the bounding box of the chunks of an infinite layer
+const MAX_CHUNKED_AREA: usize = 1 << 26;
+
+/// A Tiled map
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Map {
+    /// Width of the map, in tiles (not meaningful for infinite maps)
+    pub width: usize,
+    /// Height of the map, in tiles (not meaningful for infinite maps)
+    pub height: usize,
+    /// Width of a tile, in pixels
+    pub tile_width: u32,
+    /// Height of a tile, in pixels
+    pub tile_height: u32,
+    /// Whether the map is infinite, in which case the layers are stored as chunks
+    pub infinite: bool,
+    /// References to the tilesets used by the map
+    pub tilesets: Vec<Tileset>,
+    /// Tile layers, from bottom to top
+    pub layers: Vec<TileLayer>,
+}
+
+/// Reference to a tileset of a [`Map`]
+///
+/// Only the first gid and the source (or name) of the tileset are retained.
+/// The definition of an embedded tileset is lost when importing, and cannot be exported.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Tileset {
+    /// Global id of the first tile of the tileset
+    pub first_gid: u32,
+    /// Path to the external tileset file, or `None` if the tileset is embedded in the map
+    pub source: Option<String>,
+    /// Name of the tileset, if it is embedded in the map
+    pub name: Option<String>,
+}
+
+/// A tile layer of a [`Map`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct TileLayer {
+    /// Name of the layer
+    pub name: String,
+    /// Column of the map where the first column of `tiles` is
+    ///
+    /// It is `0` for finite maps, and may be negative for infinite maps.
+    pub x: i32,
+    /// Row of the map where the first row of `tiles` is
+    ///
+    /// It is `0` for finite maps, and may be negative for infinite maps.
+    pub y: i32,
+    /// Tiles of the layer, `None` for empty cells
+    pub tiles: DynamicGrid<Option<TileId>>,
+}
+
+/// Global id of a tile, with its flip flags
+///
+/// The global id identifies both the tileset and the tile within it (see [`Map::tileset_of`]).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TileId(NonZeroU32);
+
+/// Encoding of the layer data when exporting a [`Map`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DataEncoding {
+    /// Comma-separated global ids (an array of numbers in JSON)
+    Csv,
+    /// Uncompressed base64
+    Base64,
+    /// Base64 of the zlib-compressed data
+    Base64Zlib,
+    /// Base64 of the gzip-compressed data
+    Base64Gzip,
+}
+
+/// Error returned when a Tiled map cannot be imported
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct TiledError {
+    kind: TiledErrorKind,
+}
+
+#[derive(Debug)]
+enum TiledErrorKind {
+    Xml(roxmltree::Error),
+    Json(serde_json::Error),
+    Base64(base64::DecodeError),
+    Decompression(io::Error),
+    Invalid(String),
+}
+
+struct Chunk {
+    x: i32,
+    y: i32,
+    width: usize,
+    height: usize,
+    gids: Vec<u32>,
+}
+
+impl Map {
+    /// Create an empty finite map of `width` x `height` tiles of `tile_width` x `tile_height` pixels
+    #[must_use]
+    pub fn new(width: usize, height: usize, tile_width: u32, tile_height: u32) -> Self {
+        Self {
+            width,
+            height,
+            tile_width,
+            tile_height,
+            infinite: false,
+            tilesets: Vec::new(),
+            layers: Vec::new(),
+        }
+    }
+
+    /// Returns the tileset containing `tile`, and the local id of the tile within that tileset
+    ///
+    /// Returns `None` if no tileset contains the tile.
+    #[must_use]
+    pub fn tileset_of(&self, tile: TileId) -> Option<(&Tileset, u32)> {
+        let gid = tile.gid();
+        self.tilesets
+            .iter()
+            .filter(|tileset| tileset.first_gid <= gid)
+            .max_by_key(|tileset| tileset.first_gid)
+            .map(|tileset| (tileset, gid - tileset.first_gid))
+    }
+
+    /// Import a map from the TMX (XML) format
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TiledError`] if the XML is invalid, if the map is not orthogonal,
+    /// or if the layer data cannot be decoded or does not match the layer size.
+    /// The chunks of a layer of an infinite map must fit in an area of 2<sup>26</sup> tiles.
+    pub fn from_tmx(tmx: &str) -> Result<Self, TiledError> {
+        let document = roxmltree::Document::parse(tmx).map_err(TiledError::xml)?;
+        let root = document.root_element();
+        if !root.has_tag_name("map") {
+            return Err(TiledError::invalid("the root element is not a map"));
+        }
+        check_orientation(root.attribute("orientation"))?;
+        let mut map = Self::new(
+            attribute(root, "width")?,
+            attribute(root, "height")?,
+            attribute(root, "tilewidth")?,
+            attribute(root, "tileheight")?,
+        );
+        map.infinite = root.attribute("infinite") == Some("1");
+        for tileset in root.children().filter(|node| node.has_tag_name("tileset")) {
+            map.tilesets.push(Tileset {
+                first_gid: attribute(tileset, "firstgid")?,
+                source: tileset.attribute("source").map(String::from),
+                name: tileset.attribute("name").map(String::from),
+            });
+        }
+        read_tmx_layers(root, map.infinite, &mut map.layers)?;
+        Ok(map)
+    }
+
+    /// Import a map from the JSON format
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TiledError`] if the JSON is invalid, if the map is not orthogonal,
+    /// or if the layer data cannot be decoded or does not match the layer size.
+    /// The chunks of a layer of an infinite map must fit in an area of 2<sup>26</sup> tiles.
+    pub fn from_json(json: &str) -> Result<Self, TiledError> {
+        let root: Value = serde_json::from_str(json).map_err(TiledError::json)?;
+        check_orientation(root.get("orientation").and_then(Value::as_str))?;
+        let mut map = Self::new(
+            json::number(&root, "width")?,
+            json::number(&root, "height")?,
+            json::number(&root, "tilewidth")?,
+            json::number(&root, "tileheight")?,
+        );
+        map.infinite = root.get("infinite").and_then(Value::as_bool) == Some(true);
+        for tileset in json::array(&root, "tilesets")? {
+            map.tilesets.push(Tileset {
+                first_gid: json::number(tileset, "firstgid")?,
+                source: json::string(tileset, "source"),
+                name: json::string(tileset, "name"),
+            });
+        }
+        read_json_layers(json::array(&root, "layers")?, map.infinite, &mut map.layers)?;
+        Ok(map)
+    }
+
+    /// Export the map to the TMX (XML) format, encoding the layer data with `encoding`
+    ///
+    /// The layers of infinite maps are written as a single chunk each.
+    #[must_use]
+    pub fn to_tmx(&self, encoding: DataEncoding) -> String {
+        let mut tmx = String::new();
+        // Writing to a `String` cannot fail
+        let _ = self.write_tmx(&mut tmx, encoding);
+        tmx
+    }
+
+    fn write_tmx(&self, tmx: &mut String, encoding: DataEncoding) -> core::fmt::Result {
+        writeln!(tmx, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        write!(
+            tmx,
+            "<map version=\"1.10\" orientation=\"orthogonal\" renderorder=\"right-down\""
+        )?;
+        write!(tmx, " width=\"{}\" height=\"{}\"", self.width, self.height)?;
+        write!(
+            tmx,
+            " tilewidth=\"{}\" tileheight=\"{}\"",
+            self.tile_width, self.tile_height
+        )?;
+        writeln!(
+            tmx,
+            " infinite=\"{}\" nextlayerid=\"{}\" nextobjectid=\"1\">",
+            u8::from(self.infinite),
+            self.layers.len() + 1,
+        )?;
+        for tileset in &self.tilesets {
+            write!(tmx, " <tileset firstgid=\"{}\"", tileset.first_gid)?;
+            if let Some(source) = &tileset.source {
+                write!(tmx, " source=\"{}\"", escape(source))?;
+            }
+            if let Some(name) = &tileset.name {
+                write!(tmx, " name=\"{}\"", escape(name))?;
+            }
+            tmx.push_str("/>\n");
+        }
+        let (encoding_name, compression) = encoding.names();
+        let mut data_attributes = format!("encoding=\"{encoding_name}\"");
+        if let Some(compression) = compression {
+            write!(data_attributes, " compression=\"{compression}\"")?;
+        }
+        for (index, layer) in self.layers.iter().enumerate() {
+            let width = layer.tiles.width;
+            let height = layer.tiles.height_or_zero();
+            writeln!(
+                tmx,
+                " <layer id=\"{}\" name=\"{}\" width=\"{width}\" height=\"{height}\">",
+                index + 1,
+                escape(&layer.name),
+            )?;
+            if self.infinite {
+                write!(
+                    tmx,
+                    "  <data {data_attributes}>\n   <chunk x=\"{}\" y=\"{}\"",
+                    layer.x, layer.y,
+                )?;
+                write!(
+                    tmx,
+                    " width=\"{width}\" height=\"{height}\">{}</chunk>\n  </data>\n",
+                    encode_data(&layer.tiles, encoding),
+                )?;
+            } else {
+                writeln!(
+                    tmx,
+                    "  <data {data_attributes}>{}</data>",
+                    encode_data(&layer.tiles, encoding)
+                )?;
+            }
+            tmx.push_str(" </layer>\n");
+        }
+        tmx.push_str("</map>\n");
+        Ok(())
+    }
+
+    /// Export the map to the JSON format, encoding the layer data with `encoding`
+    ///
+    /// The layers of infinite maps are written as a single chunk each.
+    #[must_use]
+    pub fn to_json(&self, encoding: DataEncoding) -> String {
+        let tilesets: Vec<Value> = self
+            .tilesets
+            .iter()
+            .map(|tileset| {
+                let mut value = json!({ "firstgid": tileset.first_gid });
+                if let Some(source) = &tileset.source {
+                    value["source"] = json!(source);
+                }
+                if let Some(name) = &tileset.name {
+                    value["name"] = json!(name);
+                }
+                value
+            })
+            .collect();
+        let (encoding_name, compression) = encoding.names();
+        let layers: Vec<Value> = self
+            .layers
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| {
+                let width = layer.tiles.width;
+                let height = layer.tiles.height_or_zero();
+                let data = if encoding == DataEncoding::Csv {
+                    json!(layer
+                        .tiles
+                        .cells
+                        .iter()
+                        .copied()
+                        .map(raw)
+                        .collect::<Vec<_>>())
+                } else {
+                    json!(encode_data(&layer.tiles, encoding))
+                };
+                let mut value = json!({
+                    "id": index + 1,
+                    "name": layer.name,
+                    "type": "tilelayer",
+                    "x": 0,
+                    "y": 0,
+                    "width": width,
+                    "height": height,
+                    "opacity": 1,
+                    "visible": true,
+                    "encoding": encoding_name,
+                    "compression": compression.unwrap_or_default(),
+                });
+                if self.infinite {
+                    value["startx"] = json!(layer.x);
+                    value["starty"] = json!(layer.y);
+                    value["chunks"] = json!([{
+                        "x": layer.x,
+                        "y": layer.y,
+                        "width": width,
+                        "height": height,
+                        "data": data,
+                    }]);
+                } else {
+                    value["data"] = data;
+                }
+                value
+            })
+            .collect();
+        json!({
+            "type": "map",
+            "version": "1.10",
+            "orientation": "orthogonal",
+            "renderorder": "right-down",
+            "width": self.width,
+            "height": self.height,
+            "tilewidth": self.tile_width,
+            "tileheight": self.tile_height,
+            "infinite": self.infinite,
+            "nextlayerid": self.layers.len() + 1,
+            "nextobjectid": 1,
+            "tilesets": tilesets,
+            "layers": layers,
+        })
+        .to_string()
+    }
+}
+
+impl Tileset {
+    /// Create a reference to the external tileset at `source`, starting at `first_gid`
+    #[must_use]
+    pub fn new(first_gid: u32, source: impl Into<String>) -> Self {
+        Self {
+            first_gid,
+            source: Some(source.into()),
+            name: None,
+        }
+    }
+}
+
+impl TileLayer {
+    /// Create a layer named `name`, positioned at the origin of the map
+    #[must_use]
+    pub fn new(name: impl Into<String>, tiles: DynamicGrid<Option<TileId>>) -> Self {
+        Self {
+            name: name.into(),
+            x: 0,
+            y: 0,
+            tiles,
+        }
+    }
+}
+
+impl TileId {
+    /// Create a tile id from a global id, without any flip flag
+    ///
+    /// Returns `None` if `gid` is `0` (which is an empty cell in Tiled) or if it uses the bits reserved for the flags.
+    #[must_use]
+    pub fn new(gid: u32) -> Option<Self> {
+        if gid & FLAGS == 0 {
+            NonZeroU32::new(gid).map(Self)
+        } else {
+            None
+        }
+    }
+
+    /// Create a tile id from a raw value as stored by Tiled, where the highest bits are the flip flags
+    ///
+    /// Returns `None` if the global id is `0` (an empty cell).
+    #[must_use]
+    pub fn from_raw(raw: u32) -> Option<Self> {
+        if raw & !FLAGS == 0 {
+            None
+        } else {
+            NonZeroU32::new(raw).map(Self)
+        }
+    }
+
+    /// Returns the raw value as stored by Tiled, including the flip flags
+    #[must_use]
+    pub fn raw(self) -> u32 {
+        self.0.get()
+    }
+
+    /// Returns the global id, without the flip flags
+    #[must_use]
+    pub fn gid(self) -> u32 {
+        self.0.get() & !FLAGS
+    }
+
+    /// Returns true if the tile is flipped horizontally
+    #[must_use]
+    pub fn is_flipped_horizontally(self) -> bool {
+        self.0.get() & FLIPPED_HORIZONTALLY != 0
+    }
+
+    /// Returns true if the tile is flipped vertically
+    #[must_use]
+    pub fn is_flipped_vertically(self) -> bool {
+        self.0.get() & FLIPPED_VERTICALLY != 0
+    }
+
+    /// Returns true if the tile is flipped diagonally (its x and y axes are swapped)
+    #[must_use]
+    pub fn is_flipped_diagonally(self) -> bool {
+        self.0.get() & FLIPPED_DIAGONALLY != 0
+    }
+
+    /// Returns the same tile, flipped horizontally or not
+    #[must_use]
+    pub fn with_horizontal_flip(self, flipped: bool) -> Self {
+        self.with_flag(FLIPPED_HORIZONTALLY, flipped)
+    }
+
+    /// Returns the same tile, flipped vertically or not
+    #[must_use]
+    pub fn with_vertical_flip(self, flipped: bool) -> Self {
+        self.with_flag(FLIPPED_VERTICALLY, flipped)
+    }
+
+    /// Returns the same tile, flipped diagonally or not
+    #[must_use]
+    pub fn with_diagonal_flip(self, flipped: bool) -> Self {
+        self.with_flag(FLIPPED_DIAGONALLY, flipped)
+    }
+
+    fn with_flag(self, flag: u32, value: bool) -> Self {
+        let raw = if value {
+            self.0.get() | flag
+        } else {
+            self.0.get() & !flag
+        };
+        Self(NonZeroU32::new(raw).unwrap_or(self.0))
+    }
+}
+
+impl DataEncoding {
+    fn names(self) -> (&'static str, Option<&'static str>) {
+        match self {
+            Self::Csv => ("csv", None),
+            Self::Base64 => ("base64", None),
+            Self::Base64Zlib => ("base64", Some("zlib")),
+            Self::Base64Gzip => ("base64", Some("gzip")),
+        }
+    }
+}
+
+fn raw(tile: Option<TileId>) -> u32 {
+    tile.map_or(0, TileId::raw)
+}
+
+fn check_orientation(orientation: Option<&str>) -> Result<(), TiledError> {
+    match orientation {
+        None | Some("orthogonal") => Ok(()),
+        Some(orientation) => Err(TiledError::invalid(format!(
+            "unsupported orientation: {orientation}"
+        ))),
+    }
+}
+
+fn attribute<V: FromStr>(node: roxmltree::Node<'_, '_>, name: &str) -> Result<V, TiledError> {
+    let tag = node.tag_name().name();
+    let value = node
+        .attribute(name)
+        .ok_or_else(|| TiledError::invalid(format!("missing attribute {name} on <{tag}>")))?;
+    value
+        .parse()
+        .map_err(|_| TiledError::invalid(format!("invalid attribute {name} on <{tag}>: {value}")))
+}
+
+fn optional_attribute<V: FromStr + Default>(
+    node: roxmltree::Node<'_, '_>,
+    name: &str,
+) -> Result<V, TiledError> {
+    if node.has_attribute(name) {
+        attribute(node, name)
+    } else {
+        Ok(V::default())
+    }
+}
+
+fn read_tmx_layers(
+    parent: roxmltree::Node<'_, '_>,
+    infinite: bool,
+    layers: &mut Vec<TileLayer>,
+) -> Result<(), TiledError> {
+    for node in parent.children() {
+        if node.has_tag_name("layer") {
+            layers.push(read_tmx_layer(node, infinite)?);
+        } else if node.has_tag_name("group") {
+            read_tmx_layers(node, infinite, layers)?;
+        }
+    }
+    Ok(())
+}
+
+fn read_tmx_layer(node: roxmltree::Node<'_, '_>, infinite: bool) -> Result<TileLayer, TiledError> {
+    let name = node.attribute("name").unwrap_or_default().to_string();
+    let data = node
+        .children()
+        .find(|child| child.has_tag_name("data"))
+        .ok_or_else(|| TiledError::invalid(format!("missing data in layer {name}")))?;
+    let encoding = data.attribute("encoding");
+    let compression = data.attribute("compression");
+    if infinite {
+        let chunks = data
+            .children()
+            .filter(|child| child.has_tag_name("chunk"))
+            .map(|chunk| {
+                let width = attribute(chunk, "width")?;
+                let height = attribute(chunk, "height")?;
+                Ok(Chunk {
+                    x: attribute(chunk, "x")?,
+                    y: attribute(chunk, "y")?,
+                    width,
+                    height,
+                    gids: decode_tmx_data(
+                        chunk,
+                        encoding,
+                        compression,
+                        tile_count(width, height)?,
+                    )?,
+                })
+            })
+            .collect::<Result<Vec<_>, TiledError>>()?;
+        assemble_chunks(name, &chunks)
+    } else {
+        let width = attribute(node, "width")?;
+        let height = attribute(node, "height")?;
+        let gids = decode_tmx_data(data, encoding, compression, tile_count(width, height)?)?;
+        Ok(TileLayer {
+            x: optional_attribute(node, "x")?,
+            y: optional_attribute(node, "y")?,
+            tiles: to_grid(width, height, &gids)?,
+            name,
+        })
+    }
+}
+
+fn decode_tmx_data(
+    node: roxmltree::Node<'_, '_>,
+    encoding: Option<&str>,
+    compression: Option<&str>,
+    count: usize,
+) -> Result<Vec<u32>, TiledError> {
+    let text = || {
+        node.children()
+            .filter(roxmltree::Node::is_text)
+            .filter_map(|child| child.text())
+            .collect::<String>()
+    };
+    match encoding {
+        None => node
+            .children()
+            .filter(|child| child.has_tag_name("tile"))
+            .map(|tile| optional_attribute(tile, "gid"))
+            .collect(),
+        Some("csv") => text()
+            .split(',')
+            .map(str::trim)
+            .filter(|gid| !gid.is_empty())
+            .map(|gid| {
+                gid.parse()
+                    .map_err(|_| TiledError::invalid(format!("invalid tile gid: {gid}")))
+            })
+            .collect(),
+        Some("base64") => decode_base64(&text(), compression, count),
+        Some(encoding) => Err(TiledError::invalid(format!(
+            "unsupported encoding: {encoding}"
+        ))),
+    }
+}
+
+fn read_json_layers(
+    values: &[Value],
+    infinite: bool,
+    layers: &mut Vec<TileLayer>,
+) -> Result<(), TiledError> {
+    for value in values {
+        match value.get("type").and_then(Value::as_str) {
+            Some("tilelayer") => layers.push(read_json_layer(value, infinite)?),
+            Some("group") => read_json_layers(json::array(value, "layers")?, infinite, layers)?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn read_json_layer(value: &Value, infinite: bool) -> Result<TileLayer, TiledError> {
+    let name = json::string(value, "name").unwrap_or_default();
+    let encoding = value.get("encoding").and_then(Value::as_str);
+    let compression = value
+        .get("compression")
+        .and_then(Value::as_str)
+        .filter(|compression| !compression.is_empty());
+    if infinite {
+        let chunks = json::array(value, "chunks")?
+            .iter()
+            .map(|chunk| {
+                let width = json::number(chunk, "width")?;
+                let height = json::number(chunk, "height")?;
+                let count = tile_count(width, height)?;
+                Ok(Chunk {
+                    x: json::number(chunk, "x")?,
+                    y: json::number(chunk, "y")?,
+                    width,
+                    height,
+                    gids: decode_json_data(chunk.get("data"), encoding, compression, count)?,
+                })
+            })
+            .collect::<Result<Vec<_>, TiledError>>()?;
+        assemble_chunks(name, &chunks)
+    } else {
+        let width = json::number(value, "width")?;
+        let height = json::number(value, "height")?;
+        let count = tile_count(width, height)?;
+        let gids = decode_json_data(value.get("data"), encoding, compression, count)?;
+        Ok(TileLayer {
+            x: json::number(value, "x").unwrap_or_default(),
+            y: json::number(value, "y").unwrap_or_default(),
+            tiles: to_grid(width, height, &gids)?,
+            name,
+        })
+    }
+}
+
+fn decode_json_data(
+    data: Option<&Value>,
+    encoding: Option<&str>,
+    compression: Option<&str>,
+    count: usize,
+) -> Result<Vec<u32>, TiledError> {
+    match (encoding.unwrap_or("csv"), data) {
+        ("csv", Some(Value::Array(gids))) => gids
+            .iter()
+            .map(|gid| {
+                gid.as_u64()
+                    .and_then(|gid| u32::try_from(gid).ok())
+                    .ok_or_else(|| TiledError::invalid(format!("invalid tile gid: {gid}")))
+            })
+            .collect(),
+        ("base64", Some(Value::String(text))) => decode_base64(text, compression, count),
+        ("csv" | "base64", _) => Err(TiledError::invalid("invalid layer data")),
+        (encoding, _) => Err(TiledError::invalid(format!(
+            "unsupported encoding: {encoding}"
+        ))),
+    }
+}
+
+/// Decode base64 layer data, which must not contain more than `count` tiles
+///
+/// The decompressed data is read up to one byte after the expected size,
+/// so that small compressed data cannot inflate to an arbitrarily large buffer.
+fn decode_base64(
+    text: &str,
+    compression: Option<&str>,
+    count: usize,
+) -> Result<Vec<u32>, TiledError> {
+    let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(text)
+        .map_err(TiledError::base64)?;
+    let limit = u64::try_from(count.saturating_mul(4).saturating_add(1)).unwrap_or(u64::MAX);
+    let mut bytes = Vec::new();
+    match compression {
+        None => bytes = data,
+        Some("zlib") => {
+            flate2::read::ZlibDecoder::new(data.as_slice())
+                .take(limit)
+                .read_to_end(&mut bytes)
+                .map_err(TiledError::decompression)?;
+        }
+        Some("gzip") => {
+            flate2::read::GzDecoder::new(data.as_slice())
+                .take(limit)
+                .read_to_end(&mut bytes)
+                .map_err(TiledError::decompression)?;
+        }
+        Some(compression) => {
+            return Err(TiledError::invalid(format!(
+                "unsupported compression: {compression}"
+            )))
+        }
+    }
+    if bytes.len() > count.saturating_mul(4) {
+        return Err(TiledError::invalid(format!(
+            "expected {count} tiles, found more"
+        )));
+    }
+    if bytes.len() % 4 != 0 {
+        return Err(TiledError::invalid(
+            "the length of the layer data is not a multiple of 4 bytes",
+        ));
+    }
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|gid| u32::from_le_bytes([gid[0], gid[1], gid[2], gid[3]]))
+        .collect())
+}
+
+fn encode_data(tiles: &DynamicGrid<Option<TileId>>, encoding: DataEncoding) -> String {
+    if encoding == DataEncoding::Csv {
+        let rows: Vec<String> = tiles
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|tile| raw(*tile).to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect();
+        return format!("\n{}\n", rows.join(",\n"));
+    }
+    let bytes: Vec<u8> = tiles
+        .cells
+        .iter()
+        .flat_map(|tile| raw(*tile).to_le_bytes())
+        .collect();
+    let bytes = match encoding {
+        DataEncoding::Base64Zlib => {
+            let mut encoder =
+                flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&bytes).and_then(|()| encoder.finish())
+        }
+        DataEncoding::Base64Gzip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&bytes).and_then(|()| encoder.finish())
+        }
+        _ => Ok(bytes),
+    };
+    base64::engine::general_purpose::STANDARD
+        .encode(bytes.expect("writing in memory should not fail"))
+}
+
+fn to_grid(
+    width: usize,
+    height: usize,
+    gids: &[u32],
+) -> Result<DynamicGrid<Option<TileId>>, TiledError> {
+    let count = tile_count(width, height)?;
+    if gids.len() != count {
+        return Err(TiledError::invalid(format!(
+            "expected {count} tiles, found {}",
+            gids.len()
+        )));
+    }
+    Ok(DynamicGrid {
+        cells: gids.iter().copied().map(TileId::from_raw).collect(),
+        width,
+    })
+}
+
+fn tile_count(width: usize, height: usize) -> Result<usize, TiledError> {
+    width
+        .checked_mul(height)
+        .ok_or_else(|| TiledError::invalid("the layer is too large"))
+}
+
+fn assemble_chunks(name: String, chunks: &[Chunk]) -> Result<TileLayer, TiledError> {
+    let overflow = || TiledError::invalid("the chunks are too far apart");
+    let mut bounds: Option<(i64, i64, i64, i64)> = None;
+    for chunk in chunks {
+        let width = i64::try_from(chunk.width).map_err(|_| overflow())?;
+        let height = i64::try_from(chunk.height).map_err(|_| overflow())?;
+        let (x, y) = (i64::from(chunk.x), i64::from(chunk.y));
+        bounds = Some(match bounds {
+            None => (x, y, x + width, y + height),
+            Some((min_x, min_y, max_x, max_y)) => (
+                min_x.min(x),
+                min_y.min(y),
+                max_x.max(x + width),
+                max_y.max(y + height),
+            ),
+        });
+    }
+    let (min_x, min_y, max_x, max_y) = match bounds {
+        Some(bounds) => bounds,
+        None => return Ok(TileLayer::new(name, DynamicGrid::new())),
+    };
+    let width = usize::try_from(max_x - min_x).map_err(|_| overflow())?;
+    let height = usize::try_from(max_y - min_y).map_err(|_| overflow())?;
+    if width
+        .checked_mul(height)
+        .map_or(true, |area| area > MAX_CHUNKED_AREA)
+    {
+        return Err(overflow());
+    }
+    let mut tiles = DynamicGrid::new_with(width, height, |_, _| None);
+    for chunk in chunks {
+        let chunk_tiles = to_grid(chunk.width, chunk.height, &chunk.gids)?;
+        let left = usize::try_from(i64::from(chunk.x) - min_x).map_err(|_| overflow())?;
+        let top = usize::try_from(i64::from(chunk.y) - min_y).map_err(|_| overflow())?;
+        for (y, row) in chunk_tiles.rows().enumerate() {
+            let start = (top + y) * width + left;
+            tiles.cells[start..start + row.len()].copy_from_slice(row);
+        }
+    }
+    Ok(TileLayer {
+        name,
+        x: i32::try_from(min_x).map_err(|_| overflow())?,
+        y: i32::try_from(min_y).map_err(|_| overflow())?,
+        tiles,
+    })
+}
+
+impl TiledError {
+    fn xml(error: roxmltree::Error) -> Self {
+        Self {
+            kind: TiledErrorKind::Xml(error),
+        }
+    }
+
+    fn json(error: serde_json::Error) -> Self {
+        Self {
+            kind: TiledErrorKind::Json(error),
+        }
+    }
+
+    fn base64(error: base64::DecodeError) -> Self {
+        Self {
+            kind: TiledErrorKind::Base64(error),
+        }
+    }
+
+    fn decompression(error: io::Error) -> Self {
+        Self {
+            kind: TiledErrorKind::Decompression(error),
+        }
+    }
+
+    fn invalid(message: impl Into<String>) -> Self {
+        Self {
+            kind: TiledErrorKind::Invalid(message.into()),
+        }
+    }
+}
+
+impl From<InvalidField> for TiledError {
+    fn from(field: InvalidField) -> Self {
+        Self::invalid(field.message)
+    }
+}
+
+impl Display for TiledError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.kind {
+            TiledErrorKind::Xml(error) => write!(f, "Invalid XML: {error}"),
+            TiledErrorKind::Json(error) => write!(f, "Invalid JSON: {error}"),
+            TiledErrorKind::Base64(error) => write!(f, "Invalid base64 layer data: {error}"),
+            TiledErrorKind::Decompression(error) => {
+                write!(f, "Cannot decompress layer data: {error}")
+            }
+            TiledErrorKind::Invalid(message) => write!(f, "Invalid Tiled map: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for TiledError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            TiledErrorKind::Xml(error) => Some(error),
+            TiledErrorKind::Json(error) => Some(error),
+            TiledErrorKind::Base64(error) => Some(error),
+            TiledErrorKind::Decompression(error) => Some(error),
+            TiledErrorKind::Invalid(_) => None,
+        }
+    }
+}
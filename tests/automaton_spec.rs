@@ -0,0 +1,144 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use cell_grid::{Boundary, DynamicGrid, LifeRule, Neighborhood, ParseLifeRuleError};
+use common::parse;
+use rstest::rstest;
+
+#[test]
+fn blinker_oscillates() {
+    let horizontal = parse(5, "..... ..... .###. ..... .....", |c| c == '#');
+    let vertical = parse(5, "..... ..#.. ..#.. ..#.. .....", |c| c == '#');
+    let mut grid = horizontal.clone();
+    let mut buffer = DynamicGrid::new();
+    grid.step_life(&mut buffer, LifeRule::CONWAY, Boundary::Bounded);
+    assert_eq!(grid, vertical);
+    assert_eq!(buffer, horizontal);
+    grid.step_life(&mut buffer, LifeRule::CONWAY, Boundary::Bounded);
+    assert_eq!(grid, horizontal);
+}
+
+#[test]
+fn glider_wraps_around() {
+    let start = parse(6, ".#.... ..#... ###... ...... ...... ......", |c| c == '#');
+    let mut grid = start.clone();
+    let mut buffer = DynamicGrid::new();
+    for _ in 0..24 {
+        grid.step_life(&mut buffer, LifeRule::CONWAY, Boundary::Wrapping);
+    }
+    assert_eq!(grid, start);
+}
+
+#[test]
+fn neighbors_depend_on_neighborhood_and_boundary() {
+    let mut grid = DynamicGrid::new_with(3, 3, |_, _| 1);
+    let mut buffer = DynamicGrid::new();
+    grid.step_automaton(
+        &mut buffer,
+        Neighborhood::Moore,
+        Boundary::Bounded,
+        |_, n| n.iter().count(),
+    );
+    assert_eq!(grid.get(0, 0), Some(&3));
+    assert_eq!(grid.get(1, 1), Some(&8));
+    grid.step_automaton(
+        &mut buffer,
+        Neighborhood::VonNeumann,
+        Boundary::Wrapping,
+        |_, n| n.iter().count(),
+    );
+    assert!(grid.cells().all(|c| *c == 4));
+}
+
+#[test]
+fn neighbors_can_be_accessed_by_offset() {
+    let mut grid = DynamicGrid::new_with(3, 1, |x, _| x);
+    let mut buffer = DynamicGrid::new();
+    grid.step_automaton(
+        &mut buffer,
+        Neighborhood::Moore,
+        Boundary::Wrapping,
+        |_, n| *n.get(-1, 0).unwrap(),
+    );
+    assert_eq!(grid.cells().copied().collect::<Vec<_>>(), [2, 0, 1]);
+}
+
+#[test]
+fn neighbors_at_extreme_offsets_do_not_overflow() {
+    let mut grid = DynamicGrid::new_with(3, 1, |x, _| x);
+    let mut buffer = DynamicGrid::new();
+    grid.step_automaton(
+        &mut buffer,
+        Neighborhood::Moore,
+        Boundary::Bounded,
+        |_, n| {
+            n.get(isize::MAX, 0)
+                .or_else(|| n.get(isize::MIN, 0))
+                .map_or(9, |c| *c)
+        },
+    );
+    assert!(grid.cells().all(|c| *c == 9));
+    let mut grid = DynamicGrid::new_with(3, 1, |x, _| x);
+    grid.step_automaton(
+        &mut buffer,
+        Neighborhood::Moore,
+        Boundary::Wrapping,
+        |_, n| *n.get(isize::MAX, 0).unwrap(),
+    );
+    assert_eq!(grid.cells().copied().collect::<Vec<_>>(), [1, 2, 0]);
+}
+
+#[test]
+fn margolus_alternates_partitions() {
+    let mut grid = DynamicGrid::new_from_iter(4, 0..8).unwrap();
+    let rotate = |[a, b, c, d]: [&i32; 4]| [*c, *a, *d, *b];
+    grid.step_margolus(false, Boundary::Bounded, rotate);
+    assert_eq!(
+        grid.cells().copied().collect::<Vec<_>>(),
+        [4, 0, 6, 2, 5, 1, 7, 3]
+    );
+    grid.step_margolus(true, Boundary::Bounded, rotate);
+    assert_eq!(
+        grid.cells().copied().collect::<Vec<_>>(),
+        [4, 0, 6, 2, 5, 1, 7, 3]
+    );
+    grid.step_margolus(true, Boundary::Wrapping, rotate);
+    assert_eq!(
+        grid.cells().copied().collect::<Vec<_>>(),
+        [5, 6, 7, 4, 3, 0, 1, 2]
+    );
+}
+
+#[rstest]
+#[case("B3/S23", LifeRule::CONWAY)]
+#[case("b3/s23", LifeRule::CONWAY)]
+#[case("S23/B3", LifeRule::CONWAY)]
+fn can_parse_life_rule(#[case] text: &str, #[case] expected: LifeRule) {
+    assert_eq!(text.parse::<LifeRule>().unwrap(), expected);
+}
+
+#[rstest]
+fn cannot_parse_invalid_life_rule(
+    #[values("", "B3", "B3/S9", "B3/S2x", "23/3", "B3/B3")] text: &str,
+) {
+    let _: ParseLifeRuleError = text.parse::<LifeRule>().unwrap_err();
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_step_gives_same_result() {
+    let mut grid = parse(6, ".#.... ..#... ###... ...... ...##. ...##.", |c| c == '#');
+    let mut expected = grid.clone();
+    let (mut buffer, mut expected_buffer) = (DynamicGrid::new(), DynamicGrid::new());
+    for _ in 0..4 {
+        grid.par_step_automaton(
+            &mut buffer,
+            Neighborhood::Moore,
+            Boundary::Wrapping,
+            |c, n| LifeRule::CONWAY.next(*c, n.count(|c| *c)),
+        );
+        expected.step_life(&mut expected_buffer, LifeRule::CONWAY, Boundary::Wrapping);
+        assert_eq!(grid, expected);
+    }
+}
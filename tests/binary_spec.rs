@@ -0,0 +1,91 @@
+#![allow(missing_docs)]
+
+use cell_grid::{Codec, DynamicGrid};
+use rstest::rstest;
+
+#[test]
+fn rle_of_empty_grid_is_empty() {
+    let grid: DynamicGrid<u8> = DynamicGrid::new();
+    assert!(grid.encode_rle().is_empty());
+    assert_eq!(DynamicGrid::<u8>::decode_rle(0, []).unwrap(), grid);
+}
+
+#[test]
+fn rle_runs_span_rows() {
+    let grid = DynamicGrid::new_from_iter(2, [1, 1, 1, 2, 2, 2]).unwrap();
+    assert_eq!(grid.encode_rle(), [(3, 1), (3, 2)]);
+}
+
+#[test]
+fn decode_rle_rejects_incomplete_rows() {
+    assert!(DynamicGrid::decode_rle(2, [(3, 'a')]).is_err());
+}
+
+#[rstest]
+fn round_trips(#[values(Codec::Raw, Codec::Rle, Codec::Delta)] codec: Codec) {
+    let grid = DynamicGrid::new_with(7, 5, |x, y| {
+        i16::try_from(x * 1000).unwrap() - i16::try_from(y).unwrap() * 300
+    });
+    assert_eq!(
+        DynamicGrid::from_bytes(&grid.to_bytes(codec)).unwrap(),
+        grid
+    );
+    let empty: DynamicGrid<u64> = DynamicGrid::new();
+    assert_eq!(
+        DynamicGrid::from_bytes(&empty.to_bytes(codec)).unwrap(),
+        empty
+    );
+}
+
+#[test]
+fn header_is_self_describing() {
+    let grid = DynamicGrid::new_from_iter(3, [7_u16; 6]).unwrap();
+    let bytes = grid.to_bytes(Codec::Rle);
+    assert_eq!(&bytes[..4], b"CGRD");
+    assert_eq!(bytes[4..7], [1, 1, 2]);
+    assert_eq!(bytes[7..11], 3_u32.to_le_bytes());
+    assert_eq!(bytes[11..15], 2_u32.to_le_bytes());
+    assert_eq!(bytes[15..], [6, 7, 0]);
+}
+
+#[test]
+fn long_runs_use_variable_length_integers() {
+    let grid = DynamicGrid::new_with(300, 1, |_, _| 1_u8);
+    let bytes = grid.to_bytes(Codec::Rle);
+    assert_eq!(bytes[15..], [0xac, 0x02, 1]);
+    assert_eq!(DynamicGrid::from_bytes(&bytes).unwrap(), grid);
+}
+
+#[test]
+fn delta_is_compact_for_gradients() {
+    let grid = DynamicGrid::new_with(1000, 1, |x, _| u32::try_from(x).unwrap() * 3);
+    let rle = grid.to_bytes(Codec::Rle);
+    let delta = grid.to_bytes(Codec::Delta);
+    assert!(delta.len() < 30);
+    assert!(delta.len() < rle.len());
+}
+
+#[test]
+fn invalid_bytes_are_errors() {
+    let grid = DynamicGrid::new_from_iter(2, [1_u8, 2, 3, 4]).unwrap();
+    let bytes = grid.to_bytes(Codec::Rle);
+    assert!(DynamicGrid::<u8>::from_bytes(&bytes[..10]).is_err());
+    assert!(DynamicGrid::<u8>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(DynamicGrid::<u16>::from_bytes(&bytes).is_err());
+    let mut extra = bytes.clone();
+    extra.extend_from_slice(&[1, 5]);
+    assert!(DynamicGrid::<u8>::from_bytes(&extra).is_err());
+    for (index, value) in [(0, b'X'), (4, 2), (5, 9)] {
+        let mut corrupted = bytes.clone();
+        corrupted[index] = value;
+        assert!(DynamicGrid::<u8>::from_bytes(&corrupted).is_err());
+    }
+    let mut huge = b"CGRD\x01\x01\x01\xff\xff\xff\xff\xff\xff\xff\xff".to_vec();
+    huge.extend_from_slice(&[
+        0x81, 0x80, 0x80, 0x80, 0xe0, 0xff, 0xff, 0xff, 0xff, 0x01, 7,
+    ]);
+    assert!(DynamicGrid::<u8>::from_bytes(&huge).is_err());
+    let mut large = b"CGRD\x01\x01\x01\x00\x00\x01\x00\x00\x00\x01\x00".to_vec();
+    large.extend_from_slice(&[0x80, 0x80, 0x80, 0x80, 0x10, 7]);
+    assert!(DynamicGrid::<u8>::from_bytes(&large).is_err());
+}
@@ -0,0 +1,62 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use cell_grid::{DynamicGrid, Neighborhood, PathPlanner};
+use common::parse;
+
+#[test]
+fn clearance_is_the_largest_free_square() {
+    let grid = parse(4, "....  ....  ...#  ....", |c| c == '.');
+    let clearance = grid.clearance(|c| *c);
+    let expected =
+        DynamicGrid::new_from_iter(4, [3, 2, 2, 1, 3, 2, 1, 1, 2, 2, 1, 0, 1, 1, 1, 1]).unwrap();
+    assert_eq!(clearance, expected);
+}
+
+#[test]
+fn update_gives_same_result_as_full_recomputation() {
+    let mut grid = parse(5, "..... ..... ..... ..... .....", |c| c == '.');
+    let mut clearance = grid.clearance(|c| *c);
+    for (x, y, value) in [(2, 2, false), (4, 4, false), (2, 2, true), (0, 0, false)] {
+        grid.set(x, y, value);
+        grid.update_clearance(&mut clearance, x, y, 1, 1, |c| *c);
+        assert_eq!(clearance, grid.clearance(|c| *c));
+    }
+}
+
+#[test]
+fn update_accepts_rectangles_reaching_past_the_grid() {
+    let mut grid = parse(5, "..... ..... ..... ..... .....", |c| c == '.');
+    let mut clearance = grid.clearance(|c| *c);
+    grid.set(3, 3, false);
+    grid.set(4, 1, false);
+    grid.update_clearance(&mut clearance, 3, 1, usize::MAX, usize::MAX, |c| *c);
+    assert_eq!(clearance, grid.clearance(|c| *c));
+}
+
+#[test]
+fn large_units_cannot_go_through_narrow_gaps() {
+    let grid = parse(5, "..... ..... ##.## ..... .....", |c| c == '.');
+    let cost = |c: &bool| c.then(|| 1_u32);
+    let small = grid.dijkstra_map_for_size([(0, 0)], 1, Neighborhood::VonNeumann, cost);
+    let large = grid.dijkstra_map_for_size([(0, 0)], 2, Neighborhood::VonNeumann, cost);
+    assert!(small.get(0, 3).unwrap().is_some());
+    assert!(large.get(0, 3).unwrap().is_none());
+}
+
+#[test]
+fn clearance_can_be_used_with_path_planner() {
+    let grid = parse(5, "..... ..... #...# ..... .....", |c| c == '.');
+    let clearance = grid.clearance(|c| *c);
+    let mut planner = PathPlanner::new(
+        &clearance,
+        (0, 0),
+        (0, 3),
+        Neighborhood::VonNeumann,
+        |_, _| 0,
+        |c| (*c >= 2).then(|| 1_u32),
+    );
+    let path = planner.path().unwrap();
+    assert!(path.iter().all(|(x, y)| clearance.get(*x, *y) >= Some(&2)));
+}
@@ -0,0 +1,175 @@
+#![allow(missing_docs, clippy::float_cmp)]
+
+mod common;
+
+use cell_grid::collision::{Aabb, CollisionShape, Side};
+use common::parse;
+use rstest::rstest;
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn shape(cell: &char) -> CollisionShape {
+    match cell {
+        '#' => CollisionShape::Solid,
+        '=' => CollisionShape::Platform,
+        _ => CollisionShape::Empty,
+    }
+}
+
+const LEVEL: &str = "
+    .....
+    ...#.
+    .==..
+    .....
+    #####
+";
+
+#[test]
+fn free_move_is_not_blocked() {
+    let grid = parse(5, LEVEL, |c| c);
+    let result = grid.move_aabb(
+        Aabb::new([0.0, 0.0], [1.0, 1.0]),
+        [1.5, 0.0],
+        [1.0, 1.0],
+        shape,
+    );
+    assert_eq!(result.aabb, Aabb::new([1.5, 0.0], [2.5, 1.0]));
+    assert_eq!(result.velocity, [1.5, 0.0]);
+    assert!(result.contacts.is_empty());
+}
+
+#[test]
+fn stops_against_a_wall_on_the_right() {
+    let grid = parse(5, LEVEL, |c| c);
+    let result = grid.move_aabb(
+        Aabb::new([0.5, 1.25], [1.5, 1.75]),
+        [4.0, 0.0],
+        [1.0, 1.0],
+        shape,
+    );
+    assert_eq!(result.aabb.max[0], 3.0);
+    assert_eq!(result.velocity, [0.0, 0.0]);
+    assert_eq!(result.contacts.len(), 1);
+    assert_eq!(result.contacts[0].cell, (3, 1));
+    assert_eq!(result.contacts[0].side, Side::Left);
+    assert_eq!(result.contacts[0].time_of_impact, 0.375);
+}
+
+#[test]
+fn stops_against_a_wall_on_the_left() {
+    let grid = parse(5, LEVEL, |c| c);
+    let result = grid.move_aabb(
+        Aabb::new([4.25, 1.0], [4.75, 2.0]),
+        [-2.0, 0.0],
+        [1.0, 1.0],
+        shape,
+    );
+    assert_eq!(result.aabb.min[0], 4.0);
+    assert_eq!(result.contacts[0].side, Side::Right);
+}
+
+#[test]
+fn reports_all_cells_touched_on_landing() {
+    let grid = parse(5, LEVEL, |c| c);
+    let result = grid.move_aabb(
+        Aabb::new([3.5, 3.0], [4.5, 3.5]),
+        [0.0, 1.0],
+        [1.0, 1.0],
+        shape,
+    );
+    assert_eq!(result.aabb.max[1], 4.0);
+    let cells: Vec<_> = result.contacts.iter().map(|c| c.cell).collect();
+    assert_eq!(cells, [(3, 4), (4, 4)]);
+    assert!(result.contacts.iter().all(|c| c.side == Side::Top));
+}
+
+#[test]
+fn resolves_x_then_y() {
+    let grid = parse(5, LEVEL, |c| c);
+    let result = grid.move_aabb(
+        Aabb::new([3.25, 2.25], [3.75, 2.75]),
+        [0.5, 2.0],
+        [1.0, 1.0],
+        shape,
+    );
+    assert_eq!(result.aabb, Aabb::new([3.75, 3.5], [4.25, 4.0]));
+    assert_eq!(result.velocity, [0.5, 0.0]);
+}
+
+#[test]
+fn platforms_only_block_downward_moves() {
+    let grid = parse(5, LEVEL, |c| c);
+    let from_below = grid.move_aabb(
+        Aabb::new([1.25, 3.25], [1.75, 3.75]),
+        [0.0, -3.0],
+        [1.0, 1.0],
+        shape,
+    );
+    assert_eq!(from_below.aabb.min[1], 0.25);
+    assert!(from_below.contacts.is_empty());
+    let from_above = grid.move_aabb(
+        Aabb::new([1.25, 0.25], [1.75, 0.75]),
+        [0.0, 3.0],
+        [1.0, 1.0],
+        shape,
+    );
+    assert_eq!(from_above.aabb.max[1], 2.0);
+    assert_eq!(from_above.contacts[0].cell, (1, 2));
+    let sideways = grid.move_aabb(
+        Aabb::new([0.25, 2.25], [0.75, 2.75]),
+        [2.0, 0.0],
+        [1.0, 1.0],
+        shape,
+    );
+    assert!(sideways.contacts.is_empty());
+}
+
+#[test]
+fn respects_cell_size() {
+    let grid = parse(5, LEVEL, |c| c);
+    let result = grid.move_aabb(
+        Aabb::new([0.0, 0.0], [16.0, 16.0]),
+        [0.0, 100.0],
+        [16.0, 16.0],
+        shape,
+    );
+    assert_eq!(result.aabb.max[1], 64.0);
+}
+
+#[rstest]
+#[case([0.0, 16.0])]
+#[case([16.0, -16.0])]
+#[case([f32::NAN, 16.0])]
+#[case([16.0, f32::INFINITY])]
+fn invalid_cell_size_does_not_move(#[case] cell_size: [f32; 2]) {
+    let grid = parse(5, LEVEL, |c| c);
+    let aabb = Aabb::new([0.0, 0.0], [16.0, 16.0]);
+    let result = grid.move_aabb(aabb, [10.0, 100.0], cell_size, shape);
+    assert_eq!(result.aabb, aabb);
+    assert!(result.contacts.is_empty());
+}
+
+#[test]
+fn huge_boxes_only_check_cells_in_the_grid() {
+    let grid = parse(5, LEVEL, |c| c);
+    let result = grid.move_aabb(
+        Aabb::new([0.0, -1e30], [1.0, 1e30]),
+        [4.0, 0.0],
+        [1.0, 1.0],
+        shape,
+    );
+    assert_eq!(result.aabb.max[0], 1.0);
+    assert_eq!(result.contacts.len(), 1);
+    assert_eq!(result.contacts[0].cell, (1, 4));
+}
+
+#[rstest]
+#[case(Aabb::new([f32::NEG_INFINITY, 0.0], [1.0, 1.0]))]
+#[case(Aabb::new([0.0, 0.0], [1.0, f32::INFINITY]))]
+#[case(Aabb::new([0.0, f32::NAN], [1.0, 1.0]))]
+fn non_finite_boxes_do_not_move(#[case] aabb: Aabb) {
+    let grid = parse(5, LEVEL, |c| c);
+    let result = grid.move_aabb(aabb, [4.0, 4.0], [1.0, 1.0], shape);
+    assert_eq!(result.aabb.min[0], aabb.min[0]);
+    assert_eq!(result.aabb.max[0], aabb.max[0]);
+    assert!(result.contacts.is_empty());
+}
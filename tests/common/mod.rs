@@ -0,0 +1,6 @@
+use cell_grid::DynamicGrid;
+
+/// Create a grid of `width` columns from the characters of `map` (ignoring whitespace), mapped to cells by `cell`
+pub fn parse<T>(width: usize, map: &str, cell: impl FnMut(char) -> T) -> DynamicGrid<T> {
+    DynamicGrid::new_from_iter(width, map.chars().filter(|c| !c.is_whitespace()).map(cell)).unwrap()
+}
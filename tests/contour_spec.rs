@@ -0,0 +1,87 @@
+#![allow(missing_docs, clippy::float_cmp)]
+
+use cell_grid::{Contour, DynamicGrid};
+
+fn signed_area(contour: &Contour) -> f32 {
+    let points = &contour.points;
+    (0..points.len())
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            a[0] * b[1] - b[0] * a[1]
+        })
+        .sum::<f32>()
+        / 2.0
+}
+
+#[test]
+fn outlines_of_a_ring_have_an_outer_boundary_and_a_hole() {
+    let grid = DynamicGrid::new_with(5, 5, |x, y| {
+        (1..4).contains(&x) && (1..4).contains(&y) && (x, y) != (2, 2)
+    });
+    let outlines = grid.outlines(|c| *c);
+    assert_eq!(outlines.len(), 2);
+    assert!(outlines.iter().all(|o| o.closed && o.points.len() == 4));
+    let mut areas: Vec<f32> = outlines.iter().map(signed_area).collect();
+    areas.sort_by(f32::total_cmp);
+    assert_eq!(areas, [-1.0, 9.0]);
+}
+
+#[test]
+fn cells_touching_by_a_corner_have_separate_outlines() {
+    let grid = DynamicGrid::new_from_iter(2, [true, false, false, true]).unwrap();
+    let outlines = grid.outlines(|c| *c);
+    assert_eq!(outlines.len(), 2);
+    assert!(outlines.iter().all(|o| signed_area(o) == 1.0));
+}
+
+#[test]
+fn contours_reaching_the_edge_are_open() {
+    let grid = DynamicGrid::new_with(3, 3, |x, _| if x == 0 { 1.0 } else { 0.0 });
+    let contours = grid.contours(|c| *c, 0.5);
+    assert_eq!(contours.len(), 1);
+    assert!(!contours[0].closed);
+    assert_eq!(contours[0].points, [[1.0, 0.5], [1.0, 1.5], [1.0, 2.5]]);
+}
+
+#[test]
+fn closed_contours_are_clockwise_around_high_values() {
+    let grid = DynamicGrid::new_with(9, 9, |x, y| {
+        let (dx, dy) = (x.abs_diff(4), y.abs_diff(4));
+        f32::from(u8::try_from(8 - dx.max(dy)).unwrap())
+    });
+    for level in [4.5, 5.5, 6.5, 7.5] {
+        let contours = grid.contours(|c| *c, level);
+        assert_eq!(contours.len(), 1);
+        assert!(contours[0].closed);
+        assert!(signed_area(&contours[0]) > 0.0);
+    }
+}
+
+#[test]
+fn saddles_are_resolved_with_the_center_value() {
+    let grid = DynamicGrid::new_from_iter(2, [1.0, 0.0, 0.0, 1.0]).unwrap();
+    let connected = grid.contours(|c| *c, 0.4);
+    assert_eq!(connected.len(), 2);
+    assert_eq!(connected[0].points, [[1.1, 0.5], [1.5, 0.9]]);
+    let separated = grid.contours(|c| *c, 0.6);
+    assert_eq!(separated.len(), 2);
+    assert_eq!(separated[0].points, [[0.9, 0.5], [0.5, 0.9]]);
+}
+
+#[test]
+fn nan_is_below_the_level() {
+    let grid = DynamicGrid::new_from_iter(2, [f32::NAN, 1.0, 1.0, 1.0]).unwrap();
+    let contours = grid.contours(|c| *c, 0.5);
+    assert_eq!(contours.len(), 1);
+    assert_eq!(contours[0].points.len(), 2);
+}
+
+#[test]
+fn simplify_keeps_the_endpoints_of_open_polylines() {
+    let grid = DynamicGrid::new_with(6, 3, |x, y| if x + y < 4 { 1.0 } else { 0.0 });
+    let contour = &grid.contours(|c| *c, 0.5)[0];
+    let simplified = contour.simplify(0.5);
+    assert!(simplified.points.len() < contour.points.len());
+    assert_eq!(simplified.points.first(), contour.points.first());
+    assert_eq!(simplified.points.last(), contour.points.last());
+}
@@ -0,0 +1,75 @@
+#![allow(missing_docs)]
+#![cfg(feature = "csv")]
+
+use cell_grid::{CsvOptions, DynamicGrid};
+
+#[test]
+fn csv_round_trips() {
+    let grid = DynamicGrid::new_with(3, 2, |x, y| format!("{x}, \"{y}\""));
+    let options = CsvOptions::default();
+    let mut csv = Vec::new();
+    grid.write_csv(&mut csv, &options).unwrap();
+    let read: DynamicGrid<String> = DynamicGrid::read_csv(csv.as_slice(), &options).unwrap();
+    assert_eq!(read, grid);
+}
+
+#[test]
+fn tsv_with_header() {
+    let tsv = "a\tb\n1\t2\n3\t4\n";
+    let options = CsvOptions::default().delimiter(b'\t').with_header();
+    let grid: DynamicGrid<u8> = DynamicGrid::read_csv(tsv.as_bytes(), &options).unwrap();
+    assert_eq!(grid, DynamicGrid::new_from_iter(2, [1, 2, 3, 4]).unwrap());
+    let mut written = Vec::new();
+    grid.write_csv(&mut written, &options).unwrap();
+    assert_eq!(String::from_utf8(written).unwrap(), "0\t1\n1\t2\n3\t4\n");
+}
+
+#[test]
+fn custom_quoting() {
+    let grid = DynamicGrid::new_from_iter(2, ["a", "b"]).unwrap();
+    let options = CsvOptions::default().quote(b'\'').always_quote();
+    let mut written = Vec::new();
+    grid.write_csv(&mut written, &options).unwrap();
+    assert_eq!(String::from_utf8(written).unwrap(), "'a','b'\n");
+}
+
+#[test]
+fn converters() {
+    let csv = "#,.\n.,#\n";
+    let grid =
+        DynamicGrid::read_csv_with(
+            csv.as_bytes(),
+            &CsvOptions::default(),
+            |field| match field {
+                "#" => Ok(true),
+                "." => Ok(false),
+                _ => Err("unknown tile"),
+            },
+        )
+        .unwrap();
+    assert_eq!(grid.get(1, 1), Some(&true));
+    let mut written = Vec::new();
+    grid.write_csv_with(&mut written, &CsvOptions::default(), |c| {
+        if *c { "X" } else { "_" }.to_string()
+    })
+    .unwrap();
+    assert_eq!(String::from_utf8(written).unwrap(), "X,_\n_,X\n");
+}
+
+#[test]
+fn parse_errors_report_row_and_column() {
+    let options = CsvOptions::default().with_header();
+    let error =
+        DynamicGrid::<i32>::read_csv("a,b,c\n1,2,3\n4,5,x\n".as_bytes(), &options).unwrap_err();
+    assert_eq!(error.row(), Some(3));
+    assert_eq!(error.column(), Some(3));
+    assert!(error.to_string().starts_with("At row 3, column 3"));
+}
+
+#[test]
+fn ragged_rows_report_their_row() {
+    let error =
+        DynamicGrid::<i32>::read_csv("1,2\n3\n".as_bytes(), &CsvOptions::default()).unwrap_err();
+    assert_eq!(error.row(), Some(2));
+    assert_eq!(error.column(), None);
+}
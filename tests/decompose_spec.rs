@@ -0,0 +1,58 @@
+#![allow(missing_docs)]
+
+use cell_grid::{CellRect, DecompositionMode, DynamicGrid};
+use rstest::rstest;
+
+fn grid() -> DynamicGrid<bool> {
+    DynamicGrid::new_with(9, 7, |x, y| (x * 5 + y * 3) % 7 < 4 || (2..6).contains(&x))
+}
+
+#[rstest]
+fn rects_exactly_cover_the_matching_cells(
+    #[values(DecompositionMode::Greedy, DecompositionMode::LargestFirst)] mode: DecompositionMode,
+) {
+    let grid = grid();
+    let rects = grid.decompose_into_rects(mode, |c| *c);
+    let mut coverage = DynamicGrid::new_with(grid.width(), grid.height(), |_, _| 0);
+    for rect in &rects {
+        assert!(rect.area() > 0);
+        for y in rect.y..rect.y + rect.height {
+            for x in rect.x..rect.x + rect.width {
+                *coverage.get_mut(x, y).unwrap() += 1;
+            }
+        }
+    }
+    let expected = DynamicGrid::new_with(grid.width(), grid.height(), |x, y| {
+        usize::from(*grid.get(x, y).unwrap())
+    });
+    assert_eq!(coverage, expected);
+}
+
+#[test]
+fn largest_first_takes_the_largest_rect_first() {
+    let grid = DynamicGrid::new_from_iter(4, "#...####".chars()).unwrap();
+    let greedy = grid.decompose_into_rects(DecompositionMode::Greedy, |c| *c == '#');
+    assert_eq!(
+        greedy,
+        [CellRect::new(0, 0, 1, 2), CellRect::new(1, 1, 3, 1)]
+    );
+    let largest = grid.decompose_into_rects(DecompositionMode::LargestFirst, |c| *c == '#');
+    assert_eq!(
+        largest,
+        [CellRect::new(0, 1, 4, 1), CellRect::new(0, 0, 1, 1)]
+    );
+}
+
+#[test]
+fn largest_empty_rect() {
+    let grid = DynamicGrid::new_from_iter(5, "#.....#...#....".chars()).unwrap();
+    assert_eq!(
+        grid.largest_empty_rect(|c| *c == '.'),
+        Some(CellRect::new(2, 0, 3, 3))
+    );
+    assert_eq!(grid.largest_empty_rect(|c| *c == '?'), None);
+    assert_eq!(
+        DynamicGrid::<char>::new().largest_empty_rect(|_| true),
+        None
+    );
+}
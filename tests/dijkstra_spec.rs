@@ -0,0 +1,143 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use cell_grid::{DynamicGrid, Neighborhood};
+use common::parse;
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn walkable(cell: &bool) -> Option<u32> {
+    cell.then(|| 1)
+}
+
+#[test]
+fn distances_go_around_walls() {
+    let grid = parse(3, "... ##. ...", |c| c == '.');
+    let map = grid.dijkstra_map([(0, 0)], Neighborhood::VonNeumann, walkable);
+    assert_eq!(map.get(0, 2), Some(&Some(6)));
+    assert_eq!(map.get(0, 1), Some(&None));
+}
+
+#[test]
+fn distances_are_to_the_nearest_goal() {
+    let grid = parse(5, ".....", |c| c == '.');
+    let map = grid.dijkstra_map([(0, 0), (4, 0)], Neighborhood::VonNeumann, walkable);
+    let distances: Vec<_> = map.cells().copied().collect();
+    assert_eq!(distances, [Some(0), Some(1), Some(2), Some(1), Some(0)]);
+}
+
+#[test]
+fn moore_neighborhood_allows_diagonal_moves() {
+    let grid = parse(3, "... ... ...", |c| c == '.');
+    let map = grid.dijkstra_map([(0, 0)], Neighborhood::Moore, walkable);
+    assert_eq!(map.get(2, 2), Some(&Some(2)));
+}
+
+#[test]
+fn unreachable_cells_are_none() {
+    let grid = parse(3, ".#.", |c| c == '.');
+    let map = grid.dijkstra_map([(0, 0)], Neighborhood::Moore, walkable);
+    assert_eq!(map.get(2, 0), Some(&None));
+}
+
+#[test]
+fn overflowing_distances_are_unreachable() {
+    let grid = DynamicGrid::new_with(300, 1, |_, _| ());
+    let map = grid.dijkstra_map([(0, 0)], Neighborhood::VonNeumann, |()| Some(1_u8));
+    assert_eq!(map.get(255, 0), Some(&Some(255)));
+    assert_eq!(map.get(256, 0), Some(&None));
+    assert_eq!(map.get(299, 0), Some(&None));
+}
+
+#[test]
+fn flow_field_leads_to_the_goal() {
+    let grid = parse(3, "... ##. ...", |c| c == '.');
+    let map = grid.dijkstra_map([(0, 2)], Neighborhood::VonNeumann, walkable);
+    let flow = map.flow_field(Neighborhood::VonNeumann);
+    let mut position = (0, 0);
+    let mut steps = 0;
+    while let Some(next) = flow.get(position.0, position.1).copied().flatten() {
+        position = next;
+        steps += 1;
+    }
+    assert_eq!(position, (0, 2));
+    assert_eq!(steps, 6);
+}
+
+#[test]
+fn update_gives_same_result_as_full_recomputation() {
+    let mut grid = parse(5, "..... ..... ..... ..... .....", |c| c == '.');
+    let goals = [((2, 0), 0)];
+    let mut map = grid.dijkstra_map_from_seeds(goals, Neighborhood::Moore, walkable);
+    for (x, y, value) in [
+        (1, 1, false),
+        (2, 1, false),
+        (3, 1, false),
+        (2, 1, true),
+        (0, 3, false),
+    ] {
+        grid.set(x, y, value);
+        grid.update_dijkstra_map(&mut map, goals, Neighborhood::Moore, x, y, 1, 1, walkable);
+        assert_eq!(
+            map,
+            grid.dijkstra_map_from_seeds(goals, Neighborhood::Moore, walkable)
+        );
+    }
+}
+
+#[test]
+fn update_can_open_a_shortcut() {
+    let mut grid = parse(3, "... ### ...", |c| c == '.');
+    let goals = [((0, 0), 0)];
+    let mut map = grid.dijkstra_map_from_seeds(goals, Neighborhood::VonNeumann, walkable);
+    assert_eq!(map.get(0, 2), Some(&None));
+    grid.set(1, 1, true);
+    grid.update_dijkstra_map(
+        &mut map,
+        goals,
+        Neighborhood::VonNeumann,
+        1,
+        1,
+        1,
+        1,
+        walkable,
+    );
+    assert_eq!(map.get(0, 2), Some(&Some(4)));
+}
+
+#[test]
+fn update_accepts_rectangles_reaching_past_the_grid() {
+    let mut grid = parse(3, "... ### ...", |c| c == '.');
+    let goals = [((0, 0), 0)];
+    let mut map = grid.dijkstra_map_from_seeds(goals, Neighborhood::VonNeumann, walkable);
+    grid.set(1, 1, true);
+    grid.update_dijkstra_map(
+        &mut map,
+        goals,
+        Neighborhood::VonNeumann,
+        1,
+        1,
+        usize::MAX,
+        usize::MAX,
+        walkable,
+    );
+    assert_eq!(
+        map,
+        grid.dijkstra_map_from_seeds(goals, Neighborhood::VonNeumann, walkable)
+    );
+}
+
+#[test]
+fn flee_map_leads_away_from_goals() {
+    let grid = parse(7, ".......", |c| c == '.');
+    let toward = grid.dijkstra_map([(2, 0)], Neighborhood::VonNeumann, |c| c.then(|| 10_i32));
+    let flee = grid.flee_map(
+        &toward,
+        |d| d * -12 / 10,
+        Neighborhood::VonNeumann,
+        |c| c.then(|| 10),
+    );
+    let flow = flee.flow_field(Neighborhood::VonNeumann);
+    assert_eq!(flow.get(3, 0), Some(&Some((4, 0))));
+    assert_eq!(flow.get(6, 0), Some(&None));
+}
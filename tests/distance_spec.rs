@@ -0,0 +1,82 @@
+#![allow(missing_docs, clippy::float_cmp)]
+
+use cell_grid::DynamicGrid;
+
+fn features() -> DynamicGrid<bool> {
+    DynamicGrid::new_with(11, 7, |x, y| (x * 7 + y * 11) % 19 == 0)
+}
+
+fn diff(a: usize, b: usize) -> u64 {
+    u64::try_from(a.abs_diff(b)).unwrap()
+}
+
+fn brute_force(grid: &DynamicGrid<bool>, distance: impl Fn(u64, u64) -> u64) -> Vec<u64> {
+    grid.cells_with_coords()
+        .map(|((x, y), _)| {
+            grid.cells_with_coords()
+                .filter(|(_, feature)| **feature)
+                .map(|((fx, fy), _)| distance(diff(x, fx), diff(y, fy)))
+                .min()
+                .unwrap_or(u64::MAX)
+        })
+        .collect()
+}
+
+#[test]
+fn euclidean_distance_transform_is_exact() {
+    let grid = features();
+    let expected = brute_force(&grid, |dx, dy| dx * dx + dy * dy);
+    let distances = grid.squared_euclidean_distance_transform();
+    assert_eq!(distances.cells().copied().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn nearest_feature_is_a_nearest_true_cell() {
+    let grid = features();
+    let distances = grid.squared_euclidean_distance_transform();
+    let nearest = grid.nearest_feature();
+    for (((x, y), nearest), distance) in nearest.cells_with_coords().zip(distances.cells()) {
+        let (fx, fy) = nearest.unwrap();
+        assert_eq!(grid.get(fx, fy), Some(&true));
+        let (dx, dy) = (diff(x, fx), diff(y, fy));
+        assert_eq!(dx * dx + dy * dy, *distance);
+    }
+}
+
+#[test]
+fn manhattan_and_chebyshev_transforms_are_exact() {
+    let grid = features();
+    let manhattan = brute_force(&grid, |dx, dy| dx + dy);
+    let chebyshev = brute_force(&grid, u64::max);
+    let cells = |grid: DynamicGrid<u32>| grid.cells().map(|c| u64::from(*c)).collect::<Vec<_>>();
+    assert_eq!(cells(grid.manhattan_distance_transform()), manhattan);
+    assert_eq!(cells(grid.chebyshev_distance_transform()), chebyshev);
+}
+
+#[test]
+fn grid_without_features_is_unreachable() {
+    let grid = DynamicGrid::new_with(3, 2, |_, _| false);
+    assert!(grid.nearest_feature().cells().all(Option::is_none));
+    assert!(grid
+        .squared_euclidean_distance_transform()
+        .cells()
+        .all(|c| *c == u64::MAX));
+    assert!(grid
+        .manhattan_distance_transform()
+        .cells()
+        .all(|c| *c == u32::MAX));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn signed_distance_field_is_negative_inside() {
+    let grid = DynamicGrid::new_with(7, 7, |x, y| (2..5).contains(&x) && (2..5).contains(&y));
+    let sdf = grid.signed_distance_field();
+    assert_eq!(sdf.get(3, 3), Some(&-2.0));
+    assert_eq!(sdf.get(2, 2), Some(&-1.0));
+    assert_eq!(sdf.get(3, 0), Some(&2.0));
+    assert_eq!(sdf.get(0, 0), Some(&8.0_f32.sqrt()));
+    let euclidean = grid.euclidean_distance_transform();
+    assert_eq!(euclidean.get(6, 3), Some(&2.0));
+    assert_eq!(euclidean.get(3, 3), Some(&0.0));
+}
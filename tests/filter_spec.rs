@@ -0,0 +1,124 @@
+#![allow(missing_docs, clippy::float_cmp)]
+
+use cell_grid::{DynamicGrid, EdgeMode, Neighborhood};
+use rstest::rstest;
+
+fn cells<T: Copy>(grid: &DynamicGrid<T>) -> Vec<T> {
+    grid.cells().copied().collect()
+}
+
+#[rstest]
+fn identity_kernel_preserves_the_grid(
+    #[values(EdgeMode::Ignore, EdgeMode::Clamp, EdgeMode::Wrap, EdgeMode::Mirror)] edge: EdgeMode,
+) {
+    let grid = DynamicGrid::new_from_iter(3, [1, 2, 3, 4, 5, 6]).unwrap();
+    let kernel = DynamicGrid::new_from_iter(3, [0, 0, 0, 0, 1, 0, 0, 0, 0]).unwrap();
+    let result: DynamicGrid<i32> = grid.convolve(&kernel, edge);
+    assert_eq!(result, grid);
+}
+
+#[rstest]
+#[case(EdgeMode::Ignore, [3, 6, 9, 7])]
+#[case(EdgeMode::Clamp, [4, 6, 9, 11])]
+#[case(EdgeMode::Wrap, [7, 6, 9, 8])]
+#[case(EdgeMode::Mirror, [4, 6, 9, 11])]
+fn edge_modes_sample_out_of_bounds_cells(#[case] edge: EdgeMode, #[case] expected: [i32; 4]) {
+    let grid = DynamicGrid::new_from_iter(4, [1, 2, 3, 4]).unwrap();
+    let kernel = DynamicGrid::new_from_iter(3, [1, 1, 1]).unwrap();
+    let result: DynamicGrid<i32> = grid.convolve(&kernel, edge);
+    assert_eq!(cells(&result), expected);
+}
+
+#[test]
+fn mirror_reflects_beyond_the_edge_cell() {
+    let grid = DynamicGrid::new_from_iter(3, [1, 2, 3]).unwrap();
+    let kernel = DynamicGrid::new_from_iter(5, [1, 0, 0, 0, 0]).unwrap();
+    let result: DynamicGrid<i32> = grid.convolve(&kernel, EdgeMode::Mirror);
+    assert_eq!(cells(&result), [2, 1, 1]);
+}
+
+#[rstest]
+fn separable_convolution_matches_full_convolution(
+    #[values(EdgeMode::Ignore, EdgeMode::Clamp, EdgeMode::Wrap, EdgeMode::Mirror)] edge: EdgeMode,
+) {
+    let grid = DynamicGrid::new_with(5, 4, |x, y| i32::try_from(x * 7 + y * 3 % 5).unwrap());
+    let horizontal = [1, 2, 1];
+    let vertical = [-1, 0, 1];
+    let kernel = DynamicGrid::new_with(3, 3, |x, y| horizontal[x] * vertical[y]);
+    let full: DynamicGrid<i32> = grid.convolve(&kernel, edge);
+    let separable: DynamicGrid<i32> = grid.convolve_separable(&horizontal, &vertical, edge);
+    assert_eq!(full, separable);
+}
+
+#[test]
+fn box_blur_averages_the_window() {
+    let grid = DynamicGrid::new_with(3, 3, |x, y| if (x, y) == (1, 1) { 9.0 } else { 0.0 });
+    let blurred = grid.box_blur(1, EdgeMode::Ignore);
+    assert!(blurred.cells().all(|c| (c - 1.0).abs() < 1e-6));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn gaussian_blur_preserves_the_total_with_clamped_uniform_grid() {
+    let grid = DynamicGrid::new_with(6, 6, |_, _| 2.0_f32);
+    let blurred = grid.gaussian_blur(1.5, EdgeMode::Clamp);
+    assert!(blurred.cells().all(|c| (c - 2.0).abs() < 1e-5));
+    let grid = DynamicGrid::new_with(9, 9, |x, y| if (x, y) == (4, 4) { 1.0 } else { 0.0 });
+    let blurred = grid.gaussian_blur(1.0, EdgeMode::Ignore);
+    assert!(blurred.get(4, 4).unwrap() > blurred.get(3, 4).unwrap());
+    assert!(blurred.get(3, 4).unwrap() > blurred.get(3, 3).unwrap());
+    assert_eq!(blurred.get(3, 4).unwrap(), blurred.get(4, 5).unwrap());
+}
+
+#[test]
+fn rank_filters() {
+    let grid = DynamicGrid::new_from_iter(3, [5, 1, 7, 2, 100, 3, 8, 4, 6]).unwrap();
+    assert_eq!(grid.median_filter(1, EdgeMode::Ignore).get(1, 1), Some(&5));
+    assert_eq!(grid.min_filter(1, EdgeMode::Ignore).get(0, 0), Some(&1));
+    assert_eq!(grid.max_filter(1, EdgeMode::Ignore).get(0, 0), Some(&100));
+    assert_eq!(grid.min_filter(0, EdgeMode::Ignore), grid);
+}
+
+#[test]
+fn erode_and_dilate() {
+    let grid = DynamicGrid::new_with(5, 5, |x, y| (x, y) == (2, 2));
+    let dilated = grid.dilate(Neighborhood::VonNeumann, EdgeMode::Ignore);
+    assert_eq!(
+        dilated,
+        DynamicGrid::new_with(5, 5, |x, y| x.abs_diff(2) + y.abs_diff(2) <= 1)
+    );
+    assert_eq!(
+        dilated.erode(Neighborhood::VonNeumann, EdgeMode::Ignore),
+        grid
+    );
+}
+
+#[test]
+fn opening_removes_noise_and_closing_fills_holes() {
+    let square = |x: usize, y: usize| (2..7).contains(&x) && (2..7).contains(&y);
+    let noisy = DynamicGrid::new_with(9, 9, |x, y| square(x, y) || (x, y) == (8, 0));
+    assert_eq!(
+        noisy.open(Neighborhood::Moore, EdgeMode::Ignore),
+        DynamicGrid::new_with(9, 9, square)
+    );
+    let holed = DynamicGrid::new_with(9, 9, |x, y| square(x, y) && (x, y) != (4, 4));
+    assert_eq!(
+        holed.close(Neighborhood::Moore, EdgeMode::Ignore),
+        DynamicGrid::new_with(9, 9, square)
+    );
+}
+
+#[rstest]
+fn large_radii_are_clamped_to_the_grid_size(
+    #[values(EdgeMode::Ignore, EdgeMode::Clamp, EdgeMode::Wrap, EdgeMode::Mirror)] edge: EdgeMode,
+) {
+    let grid = DynamicGrid::new_from_iter(3, [5, 1, 7, 2, 100, 3]).unwrap();
+    assert_eq!(grid.min_filter(usize::MAX, edge), grid.min_filter(3, edge));
+    assert_eq!(grid.max_filter(usize::MAX, edge), grid.max_filter(3, edge));
+    assert_eq!(
+        grid.median_filter(usize::MAX / 4, edge),
+        grid.median_filter(3, edge)
+    );
+    let grid = DynamicGrid::new_from_iter(2, [1.0, 2.0, 3.0, 4.0]).unwrap();
+    assert_eq!(grid.box_blur(usize::MAX, edge), grid.box_blur(2, edge));
+}
@@ -0,0 +1,116 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use cell_grid::{FogOfWar, FovAlgorithm};
+use common::parse;
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_wall(cell: &char) -> bool {
+    *cell == '#'
+}
+
+const MAP: &str = "
+    ..........
+    ..#.......
+    .....##...
+    ..........
+    .#...#....
+    ......#...
+    ...#......
+    ..........
+";
+
+#[test]
+fn everything_is_visible_in_an_empty_room() {
+    let grid = parse(5, "..... ..... ..... ..... .....", |c| c);
+    let visible = grid.field_of_view((2, 2), 10, FovAlgorithm::SymmetricShadowcasting, is_wall);
+    assert!(visible.cells().all(|c| *c));
+}
+
+#[test]
+fn walls_are_visible_but_hide_what_is_behind() {
+    let grid = parse(5, "..... ..... .##.. ..... .....", |c| c);
+    let visible = grid.field_of_view((2, 0), 10, FovAlgorithm::SymmetricShadowcasting, is_wall);
+    assert_eq!(visible.get(2, 2), Some(&true));
+    assert_eq!(visible.get(2, 3), Some(&false));
+    assert_eq!(visible.get(2, 4), Some(&false));
+}
+
+#[test]
+fn radius_limits_the_view() {
+    let grid = parse(7, ".......", |c| c);
+    let visible = grid.field_of_view((0, 0), 3, FovAlgorithm::Permissive, is_wall);
+    let cells: Vec<bool> = visible.cells().copied().collect();
+    assert_eq!(cells, [true, true, true, true, false, false, false]);
+}
+
+#[test]
+fn symmetric_shadowcasting_is_symmetric() {
+    let grid = parse(10, MAP, |c| c);
+    let floors: Vec<(usize, usize)> = grid
+        .cells_with_coords()
+        .filter(|(_, c)| !is_wall(c))
+        .map(|(coord, _)| coord)
+        .collect();
+    for &a in &floors {
+        let from_a = grid.field_of_view(a, 20, FovAlgorithm::SymmetricShadowcasting, is_wall);
+        for &b in &floors {
+            let from_b = grid.field_of_view(b, 20, FovAlgorithm::SymmetricShadowcasting, is_wall);
+            assert_eq!(from_a.get(b.0, b.1), from_b.get(a.0, a.1), "{a:?} {b:?}");
+        }
+    }
+}
+
+#[test]
+fn algorithms_are_ordered_by_permissiveness() {
+    let grid = parse(10, MAP, |c| c);
+    let origin = (4, 3);
+    let restricted = grid.field_of_view(origin, 20, FovAlgorithm::RestrictedPrecision, is_wall);
+    let symmetric = grid.field_of_view(origin, 20, FovAlgorithm::SymmetricShadowcasting, is_wall);
+    let permissive = grid.field_of_view(origin, 20, FovAlgorithm::Permissive, is_wall);
+    for ((r, s), p) in restricted
+        .cells()
+        .zip(symmetric.cells())
+        .zip(permissive.cells())
+    {
+        assert!(!r || *s);
+        assert!(!s || *p);
+    }
+}
+
+#[test]
+fn fog_of_war_remembers_seen_cells() {
+    let grid = parse(5, "..#..", |c| c);
+    let mut fog = FogOfWar::new(5, 1);
+    fog.update(
+        &grid,
+        (0, 0),
+        10,
+        FovAlgorithm::SymmetricShadowcasting,
+        is_wall,
+    );
+    assert!(fog.is_visible(1, 0));
+    assert!(!fog.is_remembered(4, 0));
+    fog.update(
+        &grid,
+        (4, 0),
+        10,
+        FovAlgorithm::SymmetricShadowcasting,
+        is_wall,
+    );
+    assert!(!fog.is_visible(1, 0));
+    assert!(fog.is_remembered(1, 0));
+    assert!(fog.is_visible(4, 0));
+    fog.add_viewer(
+        &grid,
+        (0, 0),
+        10,
+        FovAlgorithm::SymmetricShadowcasting,
+        is_wall,
+    );
+    assert!(fog.is_visible(1, 0));
+    assert!(fog.is_visible(4, 0));
+    fog.forget();
+    assert!(fog.remembered().cells().all(|c| !c));
+}
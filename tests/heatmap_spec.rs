@@ -0,0 +1,79 @@
+#![allow(missing_docs)]
+
+use cell_grid::{Colormap, DynamicGrid, HeatmapOptions};
+use rstest::rstest;
+
+#[rstest]
+#[case(Colormap::Viridis, [68, 1, 84], [253, 231, 37])]
+#[case(Colormap::Magma, [0, 0, 4], [252, 253, 191])]
+#[case(Colormap::Grayscale, [0, 0, 0], [255, 255, 255])]
+fn colormaps_have_expected_ends(
+    #[case] colormap: Colormap,
+    #[case] start: [u8; 3],
+    #[case] end: [u8; 3],
+) {
+    assert_eq!(colormap.color(0.0), start);
+    assert_eq!(colormap.color(1.0), end);
+    assert_eq!(colormap.color(-3.0), start);
+    assert_eq!(colormap.color(7.0), end);
+    assert_eq!(colormap.color(f64::NAN), start);
+}
+
+#[test]
+fn colormaps_interpolate_between_stops() {
+    assert_eq!(Colormap::Viridis.color(0.5), [33, 145, 140]);
+    assert_eq!(Colormap::Viridis.color(0.05), [70, 19, 101]);
+    assert_eq!(Colormap::Grayscale.color(0.25), [64, 64, 64]);
+}
+
+#[test]
+fn auto_range_ignores_non_finite_values() {
+    let grid = DynamicGrid::new_from_iter(2, [2.0, 4.0, f64::INFINITY, f64::NEG_INFINITY]).unwrap();
+    let heatmap = grid.heatmap(&HeatmapOptions::default().colormap(Colormap::Grayscale));
+    let cells: Vec<_> = heatmap.cells().copied().collect();
+    assert_eq!(cells, [[0; 3], [255; 3], [255; 3], [0; 3]]);
+}
+
+#[test]
+fn fixed_range_clamps_values() {
+    let grid = DynamicGrid::new_from_iter(3, [0_u8, 100, 200]).unwrap();
+    let heatmap = grid.heatmap(
+        &HeatmapOptions::default()
+            .colormap(Colormap::Grayscale)
+            .range(50.0, 150.0),
+    );
+    let cells: Vec<_> = heatmap.cells().copied().collect();
+    assert_eq!(cells, [[0; 3], [128; 3], [255; 3]]);
+}
+
+#[test]
+fn reversed_range_reverses_the_colormap() {
+    let grid = DynamicGrid::new_from_iter(2, [0_i32, 10]).unwrap();
+    let heatmap = grid.heatmap(
+        &HeatmapOptions::default()
+            .colormap(Colormap::Magma)
+            .range(10.0, 0.0),
+    );
+    let cells: Vec<_> = heatmap.cells().copied().collect();
+    assert_eq!(cells, [[252, 253, 191], [0, 0, 4]]);
+}
+
+#[test]
+fn uniform_grid_uses_the_start_of_the_colormap() {
+    let grid = DynamicGrid::new_with(3, 2, |_, _| 1.5_f32);
+    let heatmap = grid.heatmap(&HeatmapOptions::default());
+    assert!(heatmap.cells().all(|color| *color == [68, 1, 84]));
+    assert_eq!(heatmap.width(), 3);
+}
+
+#[test]
+fn nan_cells_use_the_nan_color() {
+    let grid = DynamicGrid::new_from_iter(2, [f32::NAN, 1.0]).unwrap();
+    let heatmap = grid.heatmap(&HeatmapOptions::default().nan_color([1, 2, 3]));
+    assert_eq!(heatmap.get(0, 0), Some(&[1, 2, 3]));
+    let all_nan = DynamicGrid::new_from_iter(1, [f32::NAN]).unwrap();
+    assert_eq!(
+        all_nan.heatmap(&HeatmapOptions::default()).get(0, 0),
+        Some(&[255, 0, 255])
+    );
+}
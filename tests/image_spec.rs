@@ -0,0 +1,93 @@
+#![allow(missing_docs)]
+
+use cell_grid::DynamicGrid;
+
+#[test]
+fn pbm_round_trips_with_padding_bits() {
+    let grid = DynamicGrid::new_with(11, 3, |x, y| (x + y) % 3 == 0);
+    let pbm = grid.to_pbm();
+    assert!(pbm.starts_with(b"P4\n11 3\n"));
+    assert_eq!(pbm.len(), b"P4\n11 3\n".len() + 2 * 3);
+    assert_eq!(DynamicGrid::from_pbm(&pbm).unwrap(), grid);
+}
+
+#[test]
+fn plain_pbm_digits_do_not_need_separators() {
+    let grid = DynamicGrid::from_pbm(b"P1 4 1 # width and height\n1001").unwrap();
+    assert_eq!(
+        grid,
+        DynamicGrid::new_from_iter(4, [true, false, false, true]).unwrap()
+    );
+}
+
+#[test]
+fn pgm_round_trips() {
+    let grid = DynamicGrid::new_with(3, 2, |x, y| u8::try_from(x * 100 + y).unwrap());
+    assert_eq!(DynamicGrid::<u8>::from_pgm(&grid.to_pgm()).unwrap(), grid);
+    let grid = DynamicGrid::new_with(3, 2, |x, y| u16::try_from(x * 30000 + y).unwrap());
+    let pgm = grid.to_pgm();
+    assert!(pgm.starts_with(b"P5\n3 2\n65535\n"));
+    assert_eq!(DynamicGrid::<u16>::from_pgm(&pgm).unwrap(), grid);
+}
+
+#[test]
+fn pgm_values_are_not_scaled() {
+    let grid = DynamicGrid::<u8>::from_pgm(b"P2\n2 1\n15\n0 15\n").unwrap();
+    assert_eq!(grid.cells().copied().collect::<Vec<_>>(), [0, 15]);
+    assert!(DynamicGrid::<u8>::from_pgm(b"P2\n2 1\n1000\n0 1000\n").is_err());
+    assert!(DynamicGrid::<u8>::from_pgm(b"P2 1 1 1000 5").is_err());
+    assert!(DynamicGrid::from_ppm(b"P3 1 1 1000 5 5 5").is_err());
+    let wide = DynamicGrid::<u16>::from_pgm(b"P2\n2 1\n1000\n0 1000\n").unwrap();
+    assert_eq!(wide.cells().copied().collect::<Vec<_>>(), [0, 1000]);
+}
+
+#[test]
+fn ppm_round_trips() {
+    let grid = DynamicGrid::new_with(2, 2, |x, y| {
+        [u8::try_from(x).unwrap(), u8::try_from(y).unwrap(), 200]
+    });
+    let ppm = grid.to_ppm();
+    assert!(ppm.starts_with(b"P6\n2 2\n255\n"));
+    assert_eq!(DynamicGrid::from_ppm(&ppm).unwrap(), grid);
+}
+
+#[test]
+fn invalid_images_are_errors() {
+    assert!(DynamicGrid::from_pbm(b"P2 1 1 255 0").is_err());
+    assert!(DynamicGrid::from_pbm(b"P4 8 2\n\x00").is_err());
+    assert!(DynamicGrid::from_pbm(b"P4 18446744073709551615 1\n").is_err());
+    assert!(DynamicGrid::from_pbm(b"P4 8 18446744073709551615\n\x00").is_err());
+    assert!(DynamicGrid::<u8>::from_pgm(b"P2 2 1 255 0").is_err());
+    assert!(DynamicGrid::<u8>::from_pgm(b"P2 1 1 10 11").is_err());
+    assert!(DynamicGrid::from_pbm(b"P4 8 1\x01\x00").is_err());
+    assert!(DynamicGrid::<u8>::from_pgm(b"P5 1 1 255x\x05").is_err());
+    assert!(DynamicGrid::from_ppm(b"P6 1 1 255x\x05\x05\x05").is_err());
+    assert!(DynamicGrid::from_ppm(b"").is_err());
+}
+
+#[cfg(feature = "png")]
+#[test]
+fn png_round_trips_with_palette() {
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    enum Tile {
+        Grass,
+        Water,
+        Unknown,
+    }
+    let grid = DynamicGrid::new_with(5, 3, |x, y| if x > y { Tile::Water } else { Tile::Grass });
+    let mut png = Vec::new();
+    grid.to_png(&mut png, |tile| match tile {
+        Tile::Grass => [0, 255, 0, 255],
+        Tile::Water => [0, 0, 255, 255],
+        Tile::Unknown => [0, 0, 0, 0],
+    })
+    .unwrap();
+    let read = DynamicGrid::from_png(png.as_slice(), |color| match color {
+        [0, 255, 0, 255] => Tile::Grass,
+        [0, 0, 255, 255] => Tile::Water,
+        _ => Tile::Unknown,
+    })
+    .unwrap();
+    assert_eq!(read, grid);
+    assert!(DynamicGrid::from_png(&b"not a png"[..], |_| ()).is_err());
+}
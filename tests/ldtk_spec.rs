@@ -0,0 +1,174 @@
+#![allow(missing_docs)]
+#![cfg(feature = "ldtk")]
+
+use cell_grid::ldtk::{LayerKind, Level, Project};
+
+const PROJECT: &str = r#"{
+  "jsonVersion": "1.5.3",
+  "externalLevels": false,
+  "levels": [
+    {
+      "identifier": "Start", "worldX": -64, "worldY": 32, "pxWid": 24, "pxHei": 16,
+      "layerInstances": [
+        {
+          "__identifier": "Entities", "__type": "Entities", "__cWid": 3, "__cHei": 2, "__gridSize": 8,
+          "__pxTotalOffsetX": 0, "__pxTotalOffsetY": 0, "__tilesetDefUid": null, "entityInstances": []
+        },
+        {
+          "__identifier": "Decorations", "__type": "Tiles", "__cWid": 3, "__cHei": 2, "__gridSize": 8,
+          "__pxTotalOffsetX": 4, "__pxTotalOffsetY": -2, "__tilesetDefUid": 1,
+          "intGridCsv": [], "autoLayerTiles": [],
+          "gridTiles": [
+            { "px": [16, 0], "src": [0, 0], "f": 1, "t": 7, "d": [2] },
+            { "px": [0, 8], "src": [0, 0], "f": 3, "t": 8, "d": [3] }
+          ]
+        },
+        {
+          "__identifier": "Walls", "__type": "IntGrid", "__cWid": 3, "__cHei": 2, "__gridSize": 8,
+          "__pxTotalOffsetX": 0, "__pxTotalOffsetY": 0, "__tilesetDefUid": 2,
+          "intGridCsv": [1, 0, 2, 0, 0, 1],
+          "autoLayerTiles": [
+            { "px": [0, 0], "src": [0, 0], "f": 0, "t": 1, "d": [0, 0] },
+            { "px": [0, 0], "src": [0, 0], "f": 2, "t": 2, "d": [1, 0] },
+            { "px": [16, 8], "src": [0, 0], "f": 0, "t": 3, "d": [0, 5] }
+          ],
+          "gridTiles": []
+        }
+      ]
+    },
+    {
+      "identifier": "Cave", "worldX": 0, "worldY": 0, "pxWid": 16, "pxHei": 16,
+      "externalRelPath": "project/Cave.ldtkl", "layerInstances": null
+    }
+  ]
+}"#;
+
+#[test]
+fn reads_levels_with_their_world_position() {
+    let project = Project::from_json(PROJECT).unwrap();
+    assert_eq!(project.levels.len(), 2);
+    let level = &project.levels[0];
+    assert_eq!(level.identifier, "Start");
+    assert_eq!((level.world_x, level.world_y), (-64, 32));
+    assert_eq!((level.width, level.height), (24, 16));
+    assert_eq!(level.external_path, None);
+}
+
+#[test]
+fn ignores_entity_layers() {
+    let project = Project::from_json(PROJECT).unwrap();
+    let identifiers: Vec<&str> = project.levels[0]
+        .layers
+        .iter()
+        .map(|layer| layer.identifier.as_str())
+        .collect();
+    assert_eq!(identifiers, ["Decorations", "Walls"]);
+}
+
+#[test]
+fn reads_int_grid_values_and_auto_tiles() {
+    let project = Project::from_json(PROJECT).unwrap();
+    let walls = &project.levels[0].layers[1];
+    assert_eq!(walls.kind, LayerKind::IntGrid);
+    let values = walls.int_grid.as_ref().unwrap();
+    assert_eq!(
+        values.cells().copied().collect::<Vec<_>>(),
+        [1, 0, 2, 0, 0, 1]
+    );
+    let tiles = walls.tiles.as_ref().unwrap();
+    let top_left = tiles.get(0, 0).unwrap().unwrap();
+    assert_eq!(
+        (top_left.id, top_left.flip_x, top_left.flip_y),
+        (2, false, true)
+    );
+    assert_eq!(tiles.get(2, 1).unwrap().map(|tile| tile.id), Some(3));
+    assert_eq!(tiles.get(1, 0), Some(&None));
+}
+
+#[test]
+fn reads_tile_layers_with_offset() {
+    let project = Project::from_json(PROJECT).unwrap();
+    let level = &project.levels[0];
+    let decorations = &level.layers[0];
+    assert_eq!(decorations.kind, LayerKind::Tiles);
+    assert_eq!(decorations.int_grid, None);
+    assert_eq!(decorations.offset, [4, -2]);
+    let tiles = decorations.tiles.as_ref().unwrap();
+    let tile = tiles.get(2, 0).unwrap().unwrap();
+    assert_eq!((tile.id, tile.flip_x, tile.flip_y), (7, true, false));
+    let tile = tiles.get(0, 1).unwrap().unwrap();
+    assert_eq!((tile.id, tile.flip_x, tile.flip_y), (8, true, true));
+    assert_eq!(
+        level.cell_to_world(decorations, 2, 1),
+        [-64 + 4 + 16, 32 - 2 + 8]
+    );
+}
+
+#[test]
+fn reads_external_levels() {
+    let project = Project::from_json(PROJECT).unwrap();
+    let cave = &project.levels[1];
+    assert_eq!(cave.external_path.as_deref(), Some("project/Cave.ldtkl"));
+    assert!(cave.layers.is_empty());
+
+    let level = Level::from_json(
+        r#"{
+            "identifier": "Cave", "worldX": 0, "worldY": 0, "pxWid": 16, "pxHei": 8,
+            "layerInstances": [{
+                "__identifier": "Ground", "__type": "AutoLayer", "__cWid": 2, "__cHei": 1, "__gridSize": 8,
+                "__pxTotalOffsetX": 0, "__pxTotalOffsetY": 0, "__tilesetDefUid": 3,
+                "intGridCsv": [], "autoLayerTiles": [{ "px": [8, 0], "src": [0, 0], "f": 0, "t": 4, "d": [] }], "gridTiles": []
+            }]
+        }"#,
+    )
+    .unwrap();
+    let ground = &level.layers[0];
+    assert_eq!(ground.kind, LayerKind::AutoLayer);
+    let ids: Vec<_> = ground
+        .tiles
+        .as_ref()
+        .unwrap()
+        .cells()
+        .map(|tile| tile.map(|tile| tile.id))
+        .collect();
+    assert_eq!(ids, [None, Some(4)]);
+}
+
+#[test]
+fn reads_levels_of_all_worlds() {
+    let json = r#"{
+        "levels": [],
+        "worlds": [
+            { "identifier": "A", "levels": [{ "identifier": "A1", "worldX": 0, "worldY": 0, "pxWid": 8, "pxHei": 8, "layerInstances": [] }] },
+            { "identifier": "B", "levels": [{ "identifier": "B1", "worldX": 0, "worldY": 0, "pxWid": 8, "pxHei": 8, "layerInstances": [] }] }
+        ]
+    }"#;
+    let project = Project::from_json(json).unwrap();
+    let identifiers: Vec<&str> = project
+        .levels
+        .iter()
+        .map(|level| level.identifier.as_str())
+        .collect();
+    assert_eq!(identifiers, ["A1", "B1"]);
+}
+
+#[test]
+fn invalid_projects_are_errors() {
+    assert!(Project::from_json("[").is_err());
+    assert!(Project::from_json(r#"{ "levels": 1 }"#).is_err());
+    let wrong_size = r#"{ "levels": [{
+        "identifier": "L", "worldX": 0, "worldY": 0, "pxWid": 16, "pxHei": 8,
+        "layerInstances": [{ "__identifier": "Walls", "__type": "IntGrid", "__cWid": 2, "__cHei": 1, "__gridSize": 8, "intGridCsv": [1] }]
+    }] }"#;
+    assert!(Project::from_json(wrong_size).is_err());
+    let huge = r#"{ "levels": [{
+        "identifier": "L", "worldX": 0, "worldY": 0, "pxWid": 16, "pxHei": 8,
+        "layerInstances": [{ "__identifier": "Walls", "__type": "IntGrid", "__cWid": 4294967296, "__cHei": 4294967296, "__gridSize": 8, "intGridCsv": [1] }]
+    }] }"#;
+    assert!(Project::from_json(huge).is_err());
+    let huge_tiles = r#"{ "levels": [{
+        "identifier": "L", "worldX": 0, "worldY": 0, "pxWid": 16, "pxHei": 8,
+        "layerInstances": [{ "__identifier": "Ground", "__type": "Tiles", "__cWid": 1000000, "__cHei": 1000000, "__gridSize": 8, "__tilesetDefUid": 1, "gridTiles": [] }]
+    }] }"#;
+    assert!(Project::from_json(huge_tiles).is_err());
+}
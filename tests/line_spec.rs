@@ -0,0 +1,105 @@
+#![allow(missing_docs)]
+
+use cell_grid::{DynamicGrid, LineMode};
+use rstest::rstest;
+
+fn grid(width: usize, height: usize) -> DynamicGrid<()> {
+    DynamicGrid::new_with(width, height, |_, _| ())
+}
+
+#[test]
+fn bresenham_has_one_cell_per_step() {
+    let line: Vec<_> = grid(10, 10)
+        .cells_on_line((1, 1), (7, 3), LineMode::Bresenham)
+        .collect();
+    assert_eq!(
+        line,
+        [(1, 1), (2, 1), (3, 2), (4, 2), (5, 2), (6, 3), (7, 3)]
+    );
+}
+
+#[test]
+fn supercover_includes_every_touched_cell() {
+    let line: Vec<_> = grid(10, 10)
+        .cells_on_line((0, 0), (2, 2), LineMode::Supercover)
+        .collect();
+    assert_eq!(
+        line,
+        [(0, 0), (1, 0), (0, 1), (1, 1), (2, 1), (1, 2), (2, 2)]
+    );
+}
+
+#[test]
+fn supercover_steps_are_orthogonal() {
+    let line: Vec<_> = grid(10, 10)
+        .cells_on_line((0, 0), (5, 2), LineMode::Supercover)
+        .collect();
+    for pair in line.windows(2) {
+        let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+        assert_eq!(x0.abs_diff(x1) + y0.abs_diff(y1), 1, "{pair:?}");
+    }
+    assert_eq!(line.last(), Some(&(5, 2)));
+}
+
+#[rstest]
+fn symmetric_lines_are_the_same_in_both_directions(
+    #[values((0, 0), (3, 1), (1, 4))] a: (usize, usize),
+    #[values((5, 2), (2, 5), (4, 4))] b: (usize, usize),
+) {
+    let grid = grid(6, 6);
+    let forward: Vec<_> = grid.cells_on_line(a, b, LineMode::Symmetric).collect();
+    let mut backward: Vec<_> = grid.cells_on_line(b, a, LineMode::Symmetric).collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+}
+
+#[test]
+fn lines_are_clipped_to_the_grid() {
+    let grid = grid(3, 3);
+    let line: Vec<_> = grid
+        .cells_on_line((6, 1), (0, 1), LineMode::Bresenham)
+        .collect();
+    assert_eq!(line, [(2, 1), (1, 1), (0, 1)]);
+    let line: Vec<_> = grid
+        .cells_on_line_f32([-5.5, -5.5], [10.5, 10.5], LineMode::Bresenham)
+        .collect();
+    assert_eq!(line, [(0, 0), (1, 1), (2, 2)]);
+}
+
+#[test]
+fn float_supercover_follows_the_exact_segment() {
+    let line: Vec<_> = grid(10, 10)
+        .cells_on_line_f32([0.1, 0.9], [2.9, 1.1], LineMode::Supercover)
+        .collect();
+    assert_eq!(line, [(0, 0), (1, 0), (1, 1), (2, 1)]);
+}
+
+#[test]
+fn line_of_sight_is_blocked_by_walls_between_endpoints() {
+    let grid = DynamicGrid::new_from_iter(5, "......##.......".chars()).unwrap();
+    let blocks = |c: &char| *c == '#';
+    assert!(grid.line_of_sight((0, 0), (4, 0), blocks));
+    assert!(grid.line_of_sight((0, 1), (0, 1), blocks));
+    assert!(grid.line_of_sight((1, 1), (0, 2), blocks));
+    assert!(!grid.line_of_sight((0, 0), (4, 2), blocks));
+    assert!(!grid.line_of_sight((0, 0), (5, 0), blocks));
+    assert!(grid.line_of_sight_f32([0.5, 0.5], [4.5, 0.5], blocks));
+    assert!(!grid.line_of_sight_f32([0.5, 1.5], [4.5, 1.5], blocks));
+}
+
+#[rstest]
+fn lines_far_outside_of_the_grid_are_clipped(
+    #[values(LineMode::Bresenham, LineMode::Supercover, LineMode::Symmetric)] mode: LineMode,
+) {
+    let grid = grid(3, 3);
+    let line: Vec<_> = grid.cells_on_line((0, 1), (1 << 40, 1), mode).collect();
+    assert_eq!(line, [(0, 1), (1, 1), (2, 1)]);
+    let line: Vec<_> = grid
+        .cells_on_line((usize::MAX, usize::MAX), (0, 0), mode)
+        .collect();
+    assert_eq!(line.last(), Some(&(0, 0)));
+    let line: Vec<_> = grid
+        .cells_on_line_f32([-1e30, 0.5], [1e30, 0.5], mode)
+        .collect();
+    assert_eq!(line, [(0, 0), (1, 0), (2, 0)]);
+}
@@ -0,0 +1,157 @@
+#![allow(missing_docs)]
+
+use cell_grid::{DynamicGrid, Neighborhood, PathPlanner};
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn walkable(cell: &bool) -> Option<u32> {
+    cell.then(|| 1)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn manhattan(dx: usize, dy: usize) -> u32 {
+    (dx + dy) as u32
+}
+
+fn zero(_: usize, _: usize) -> u32 {
+    0
+}
+
+#[test]
+fn finds_path_from_start_to_goal() {
+    let grid = DynamicGrid::new_with(4, 4, |_, _| true);
+    let mut planner = PathPlanner::new(
+        &grid,
+        (0, 0),
+        (3, 3),
+        Neighborhood::VonNeumann,
+        manhattan,
+        walkable,
+    );
+    let path = planner.path().unwrap();
+    assert_eq!(path.first(), Some(&(0, 0)));
+    assert_eq!(path.last(), Some(&(3, 3)));
+    assert_eq!(path.len(), 7);
+}
+
+#[test]
+fn returns_none_when_goal_is_unreachable() {
+    let grid = DynamicGrid::new_from_iter(3, [true, false, true]).unwrap();
+    let mut planner = PathPlanner::new(&grid, (0, 0), (2, 0), Neighborhood::Moore, zero, walkable);
+    assert_eq!(planner.path(), None);
+}
+
+#[test]
+fn path_is_repaired_after_changes() {
+    let mut grid = DynamicGrid::new_with(3, 3, |_, _| true);
+    let mut planner = PathPlanner::new(
+        &grid,
+        (0, 1),
+        (2, 1),
+        Neighborhood::VonNeumann,
+        manhattan,
+        walkable,
+    );
+    assert_eq!(planner.path().unwrap().len(), 3);
+    grid.set(1, 0, false);
+    grid.set(1, 1, false);
+    planner.update(&grid, 1, 0, 1, 2, walkable);
+    assert_eq!(
+        planner.path().unwrap(),
+        [(0, 1), (0, 2), (1, 2), (2, 2), (2, 1)]
+    );
+    grid.set(1, 2, false);
+    planner.update(&grid, 1, 2, 1, 1, walkable);
+    assert_eq!(planner.path(), None);
+    grid.set(1, 1, true);
+    planner.update(&grid, 1, 1, 1, 1, walkable);
+    assert_eq!(planner.path().unwrap().len(), 3);
+}
+
+#[test]
+fn update_accepts_rectangles_reaching_past_the_grid() {
+    let mut grid = DynamicGrid::new_with(3, 3, |_, _| true);
+    let mut planner = PathPlanner::new(
+        &grid,
+        (0, 1),
+        (2, 1),
+        Neighborhood::VonNeumann,
+        manhattan,
+        walkable,
+    );
+    grid.set(1, 0, false);
+    grid.set(1, 1, false);
+    grid.set(1, 2, false);
+    planner.update(&grid, 1, 0, usize::MAX, usize::MAX, walkable);
+    assert_eq!(planner.path(), None);
+}
+
+#[test]
+fn path_cost_matches_dijkstra_while_moving_and_discovering_obstacles() {
+    let mut grid = DynamicGrid::new_with(8, 8, |_, _| true);
+    let goal = (7, 7);
+    let mut planner = PathPlanner::new(
+        &grid,
+        (0, 0),
+        goal,
+        Neighborhood::Moore,
+        |dx, dy| u32::try_from(dx.max(dy)).unwrap(),
+        walkable,
+    );
+    for _ in 0..5 {
+        let path = planner.path().unwrap();
+        planner.set_start(path[1]);
+        let wall = path[2];
+        grid.set(wall.0, wall.1, false);
+        planner.update(&grid, wall.0, wall.1, 1, 1, walkable);
+        let expected = grid.dijkstra_map([goal], Neighborhood::Moore, walkable);
+        let start = planner.start();
+        let path = planner.path().unwrap();
+        let expected = expected.get(start.0, start.1).unwrap().unwrap();
+        assert_eq!(path.len() - 1, usize::try_from(expected).unwrap());
+        assert!(path.iter().all(|(x, y)| grid.get(*x, *y) == Some(&true)));
+    }
+}
+
+#[test]
+fn overflowing_costs_are_unreachable() {
+    let grid = DynamicGrid::new_with(300, 1, |_, _| ());
+    let cost = |(): &()| Some(1_u8);
+    let mut planner = PathPlanner::new(
+        &grid,
+        (0, 0),
+        (299, 0),
+        Neighborhood::VonNeumann,
+        |_, _| 0,
+        cost,
+    );
+    assert_eq!(planner.path(), None);
+    let mut planner = PathPlanner::new(
+        &grid,
+        (0, 0),
+        (200, 0),
+        Neighborhood::VonNeumann,
+        |_, _| 0,
+        cost,
+    );
+    assert_eq!(planner.path().map(|path| path.len()), Some(201));
+}
+
+#[test]
+fn moving_the_start_many_times_does_not_overflow() {
+    let grid = DynamicGrid::new_with(100, 1, |_, _| ());
+    let heuristic = |dx: usize, dy: usize| u8::try_from(dx + dy).unwrap_or(u8::MAX);
+    let mut planner = PathPlanner::new(
+        &grid,
+        (0, 0),
+        (99, 0),
+        Neighborhood::VonNeumann,
+        heuristic,
+        |()| Some(1_u8),
+    );
+    for _ in 0..10 {
+        planner.set_start((50, 0));
+        assert_eq!(planner.path().map(|path| path.len()), Some(50));
+        planner.set_start((0, 0));
+        assert_eq!(planner.path().map(|path| path.len()), Some(100));
+    }
+}
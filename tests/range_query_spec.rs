@@ -0,0 +1,79 @@
+#![allow(missing_docs)]
+
+use cell_grid::{DynamicGrid, FenwickTree};
+
+fn grid() -> DynamicGrid<i32> {
+    DynamicGrid::new_with(7, 5, |x, y| {
+        i32::try_from((x * 13 + y * 29) % 17).unwrap() - 8
+    })
+}
+
+fn rects(grid: &DynamicGrid<i32>) -> impl Iterator<Item = (usize, usize, usize, usize)> {
+    let (width, height) = (grid.width(), grid.height());
+    (0..=width + 1).flat_map(move |x| {
+        (0..=height + 1).flat_map(move |y| {
+            (0..=width - x.min(width) + 1)
+                .flat_map(move |w| (0..=height - y.min(height) + 1).map(move |h| (x, y, w, h)))
+        })
+    })
+}
+
+#[test]
+fn fenwick_sums_match_cells_in_rect_after_updates() {
+    let mut grid = grid();
+    let mut tree: FenwickTree<i64> = grid.fenwick_tree();
+    for (x, y, value) in [(0, 0, 5), (6, 4, -3), (3, 2, 12), (3, 2, 1)] {
+        let old = grid.set(x, y, value);
+        assert_eq!(tree.set(x, y, i64::from(value)), old.map(i64::from));
+        for (x, y, w, h) in rects(&grid) {
+            let expected: i64 = grid
+                .cells_in_rect(x.min(grid.width()), y, w, h)
+                .map(|c| i64::from(*c))
+                .sum();
+            let expected = if x < grid.width() { expected } else { 0 };
+            assert_eq!(tree.sum_in_rect(x, y, w, h), expected, "{x} {y} {w} {h}");
+        }
+    }
+}
+
+#[test]
+fn fenwick_add_and_out_of_bounds() {
+    let mut tree: FenwickTree<u32> = DynamicGrid::new_with(3, 3, |_, _| 1_u8).fenwick_tree();
+    assert_eq!(tree.add(1, 1, 4), Some(5));
+    assert_eq!(tree.get(1, 1), Some(5));
+    assert_eq!(tree.sum_in_rect(0, 0, 3, 3), 13);
+    assert_eq!(tree.add(3, 0, 4), None);
+    assert_eq!(tree.set(0, 3, 4), None);
+}
+
+#[test]
+fn segment_tree_min_and_max_match_cells_in_rect_after_updates() {
+    let mut grid = grid();
+    let mut min = grid.segment_tree(Ord::min);
+    let mut max = grid.segment_tree(Ord::max);
+    for (x, y, value) in [(0, 0, -20), (6, 4, 30), (3, 2, 12), (3, 2, -1)] {
+        grid.set(x, y, value);
+        min.set(x, y, value);
+        max.set(x, y, value);
+        for (x, y, w, h) in rects(&grid) {
+            let cells = || {
+                let in_grid = x < grid.width();
+                grid.cells_in_rect(x.min(grid.width()), y, w, h)
+                    .filter(move |_| in_grid)
+                    .copied()
+            };
+            assert_eq!(min.query_rect(x, y, w, h), cells().min(), "{x} {y} {w} {h}");
+            assert_eq!(max.query_rect(x, y, w, h), cells().max(), "{x} {y} {w} {h}");
+        }
+    }
+    assert_eq!(min.get(3, 2), Some(-1));
+    assert_eq!(min.set(7, 0, 0), None);
+}
+
+#[test]
+fn empty_grid_queries() {
+    let grid = DynamicGrid::<i32>::new();
+    let tree: FenwickTree<i32> = grid.fenwick_tree();
+    assert_eq!(tree.sum_in_rect(0, 0, 2, 2), 0);
+    assert_eq!(grid.segment_tree(Ord::min).query_rect(0, 0, 2, 2), None);
+}
@@ -0,0 +1,87 @@
+#![allow(missing_docs, clippy::float_cmp)]
+
+mod common;
+
+use common::parse;
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_wall(cell: &char) -> bool {
+    *cell == '#'
+}
+
+const MAP: &str = "
+    #####
+    #...#
+    #...#
+    #####
+";
+
+#[test]
+fn hits_the_first_wall_in_direction() {
+    let grid = parse(5, MAP, |c| c);
+    let hit = grid
+        .raycast([1.5, 1.5], [0.0, 1.0], 100.0, [1.0, 1.0], is_wall)
+        .unwrap();
+    assert_eq!(hit.cell, (1, 3));
+    assert_eq!(hit.distance, 1.5);
+    assert_eq!(hit.point, [1.5, 3.0]);
+    assert_eq!(hit.normal, [0, -1]);
+}
+
+#[test]
+fn diagonal_ray_reports_crossed_face() {
+    let grid = parse(5, MAP, |c| c);
+    let hit = grid
+        .raycast([1.5, 2.25], [-1.0, -1.0], 100.0, [1.0, 1.0], is_wall)
+        .unwrap();
+    assert_eq!(hit.cell, (0, 1));
+    assert_eq!(hit.distance, 0.5);
+    assert_eq!(hit.normal, [1, 0]);
+}
+
+#[test]
+fn respects_cell_size() {
+    let grid = parse(5, MAP, |c| c);
+    let hit = grid
+        .raycast([15.0, 15.0], [1.0, 0.0], 100.0, [10.0, 10.0], is_wall)
+        .unwrap();
+    assert_eq!(hit.cell, (4, 1));
+    assert_eq!(hit.distance, 25.0);
+    assert_eq!(hit.point, [40.0, 15.0]);
+}
+
+#[test]
+fn returns_none_beyond_max_distance() {
+    let grid = parse(5, MAP, |c| c);
+    let hit = grid.raycast([1.5, 1.5], [1.0, 0.0], 2.0, [1.0, 1.0], is_wall);
+    assert_eq!(hit, None);
+}
+
+#[test]
+fn ray_starting_in_a_hit_cell_has_zero_distance() {
+    let grid = parse(5, MAP, |c| c);
+    let hit = grid
+        .raycast([0.5, 0.5], [1.0, 0.0], 10.0, [1.0, 1.0], is_wall)
+        .unwrap();
+    assert_eq!(hit.cell, (0, 0));
+    assert_eq!(hit.distance, 0.0);
+    assert_eq!(hit.normal, [0, 0]);
+}
+
+#[test]
+fn ray_can_enter_the_grid_from_outside() {
+    let grid = parse(5, MAP, |c| c);
+    let hit = grid
+        .raycast([-10.5, 1.5], [1.0, 0.0], 100.0, [1.0, 1.0], is_wall)
+        .unwrap();
+    assert_eq!(hit.cell, (0, 1));
+    assert_eq!(hit.distance, 10.5);
+    assert_eq!(hit.normal, [-1, 0]);
+}
+
+#[test]
+fn ray_leaving_the_grid_returns_none() {
+    let grid = parse(3, "...", |c| c);
+    let hit = grid.raycast([0.5, 0.5], [1.0, 0.1], f32::INFINITY, [1.0, 1.0], |_| false);
+    assert_eq!(hit, None);
+}
@@ -0,0 +1,119 @@
+#![allow(missing_docs)]
+
+use cell_grid::{CellRect, DynamicGrid, Neighborhood};
+use rstest::rstest;
+
+const MAP: &str = "\
+..#.....\
+..#.##..\
+....#...\
+###.#.##\
+....#...";
+
+fn map() -> DynamicGrid<char> {
+    DynamicGrid::new_from_iter(8, MAP.chars()).unwrap()
+}
+
+fn component_count(grid: &DynamicGrid<char>, neighborhood: Neighborhood) -> usize {
+    grid.regions(neighborhood, |c| (*c == '.').then(|| ()))
+        .count()
+}
+
+#[rstest]
+fn articulation_cells_match_brute_force(
+    #[values(Neighborhood::VonNeumann, Neighborhood::Moore)] neighborhood: Neighborhood,
+) {
+    let grid = map();
+    let count = component_count(&grid, neighborhood);
+    let expected: Vec<(usize, usize)> = grid
+        .cells_with_coords()
+        .filter(|(_, c)| **c == '.')
+        .map(|(coord, _)| coord)
+        .filter(|&(x, y)| {
+            let mut without = grid.clone();
+            without.set(x, y, '#');
+            component_count(&without, neighborhood) > count
+        })
+        .collect();
+    assert_eq!(
+        grid.articulation_cells(neighborhood, |c| *c == '.'),
+        expected
+    );
+}
+
+const ROOMS: &str = "\
+...#...\
+...#...\
+.......\
+.......\
+...#...\
+...#...";
+
+fn rooms() -> DynamicGrid<char> {
+    DynamicGrid::new_from_iter(7, ROOMS.chars()).unwrap()
+}
+
+#[test]
+fn regions_group_connected_cells_with_the_same_key() {
+    let mut grid = rooms();
+    grid.set(3, 2, '#');
+    grid.set(3, 3, '#');
+    let regions = grid.regions(Neighborhood::VonNeumann, |c| (*c == '.').then(|| ()));
+    assert_eq!(regions.count(), 2);
+    assert_eq!(regions.region(0, 0), Some(0));
+    assert_eq!(regions.region(6, 5), Some(1));
+    assert_eq!(regions.region(3, 0), None);
+    assert_eq!(regions.region(7, 0), None);
+    assert_eq!(regions.size(1), Some(18));
+    assert_eq!(regions.size(2), None);
+    grid.set(3, 3, '.');
+    let moore = grid.regions(Neighborhood::Moore, |c| (*c == '.').then(|| ()));
+    assert_eq!(moore.count(), 1);
+}
+
+#[test]
+fn adjacency_reports_touching_regions_and_their_border() {
+    let grid = DynamicGrid::new_from_iter(3, "aab.ab.cc".chars()).unwrap();
+    let regions = grid.regions(Neighborhood::VonNeumann, |c| (*c != '.').then(|| *c));
+    assert_eq!(regions.count(), 3);
+    let adjacency = regions.adjacency();
+    let pairs: Vec<_> = adjacency.iter().map(|a| a.regions).collect();
+    assert_eq!(pairs, [(0, 1), (0, 2), (1, 2)]);
+    assert_eq!(adjacency[0].border, [((1, 0), (2, 0)), ((1, 1), (2, 1))]);
+    assert_eq!(adjacency[1].border, [((1, 1), (1, 2))]);
+    assert_eq!(adjacency[2].border, [((2, 1), (2, 2))]);
+}
+
+#[rstest]
+fn chokepoints_are_narrow_passages_that_disconnect_regions(
+    #[values(Neighborhood::VonNeumann, Neighborhood::Moore)] neighborhood: Neighborhood,
+) {
+    let grid = rooms();
+    let walkable = |c: &char| *c == '.';
+    assert_eq!(grid.chokepoints(neighborhood, 1, walkable), []);
+    assert_eq!(
+        grid.chokepoints(neighborhood, 2, walkable),
+        [CellRect::new(3, 2, 1, 2)]
+    );
+}
+
+#[test]
+fn chokepoints_of_width_one_are_articulation_cells() {
+    let grid = map();
+    let walkable = |c: &char| *c == '.';
+    let articulation = grid.articulation_cells(Neighborhood::VonNeumann, walkable);
+    let chokepoints = grid.chokepoints(Neighborhood::VonNeumann, 1, walkable);
+    assert!(!chokepoints.is_empty());
+    for rect in chokepoints {
+        assert!(articulation.contains(&(rect.x, rect.y)));
+    }
+}
+
+#[test]
+fn chokepoints_of_a_large_grid() {
+    let grid = DynamicGrid::new_with(500, 500, |x, y| x == 0 || y % 2 == 0);
+    let chokepoints = grid.chokepoints(Neighborhood::VonNeumann, 1, |c| *c);
+    assert_eq!(chokepoints.len(), 249 + 250 * 498);
+    assert_eq!(chokepoints[0], CellRect::new(1, 0, 1, 1));
+    assert_eq!(chokepoints[498], CellRect::new(0, 1, 1, 1));
+}
@@ -0,0 +1,61 @@
+#![allow(missing_docs)]
+
+use cell_grid::{CellStyle, DynamicGrid};
+
+fn style(c: char) -> CellStyle {
+    match c {
+        '#' => CellStyle::new().fill("black"),
+        'x' => CellStyle::new().stroke("red").label("<x>"),
+        _ => CellStyle::new(),
+    }
+}
+
+#[test]
+fn empty_grid_is_an_empty_svg() {
+    let grid: DynamicGrid<char> = DynamicGrid::new();
+    assert_eq!(
+        grid.to_svg(|c| style(*c)).to_string(),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"0\" height=\"0\" viewBox=\"0 0 0 0\">\n</svg>\n"
+    );
+}
+
+#[test]
+fn svg_draws_styled_cells_only() {
+    let grid = DynamicGrid::new_from_iter(2, ['#', '.', '.', 'x']).unwrap();
+    let svg = grid.to_svg(|c| style(*c)).to_string();
+    assert_eq!(svg.matches("<rect").count(), 2);
+    assert!(svg.contains(r#"<rect x="0" y="0" width="20" height="20" fill="black"/>"#));
+    assert!(
+        svg.contains(r#"<rect x="20" y="20" width="20" height="20" fill="none" stroke="red"/>"#)
+    );
+    assert!(svg.contains(r#"<text x="30" y="30""#));
+    assert!(svg.contains(">&lt;x&gt;</text>"));
+    assert!(!svg.contains("<path"));
+    assert!(!svg.contains("<polyline"));
+}
+
+#[test]
+fn svg_draws_grid_lines_and_paths() {
+    let grid = DynamicGrid::new_from_iter(2, ['.'; 2]).unwrap();
+    let svg = grid
+        .to_svg(|c| style(*c))
+        .cell_size(4.0)
+        .grid_lines("#ccc")
+        .path([(0, 0), (1, 0)], "blue")
+        .path([(1, 0)], "green")
+        .to_string();
+    assert!(svg.contains(r##"<path d="M0 0V4M4 0V4M8 0V4M0 0H8M0 4H8" fill="none" stroke="#ccc""##));
+    assert!(svg.contains(r#"<polyline points="2,2 6,2" fill="none" stroke="blue""#));
+    assert!(svg.contains(r#"<polyline points="6,2" fill="none" stroke="green""#));
+}
+
+#[test]
+fn html_table_has_one_row_per_grid_row() {
+    let grid = DynamicGrid::new_from_iter(2, ['#', '.', '.', 'x']).unwrap();
+    let html = grid.to_html_table(|c| style(*c));
+    assert!(html.starts_with("<table"));
+    assert_eq!(html.matches("<tr>").count(), 2);
+    assert_eq!(html.matches("<td").count(), 4);
+    assert!(html.contains("background:black\"></td>"));
+    assert!(html.contains("border:1px solid red\">&lt;x&gt;</td>"));
+}
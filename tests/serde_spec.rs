@@ -0,0 +1,74 @@
+#![allow(missing_docs)]
+#![cfg(feature = "serde")]
+
+use cell_grid::DynamicGrid;
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn grid_is_serialized_with_its_size() {
+    let grid = DynamicGrid::new_from_iter(2, [1, 2, 3, 4, 5, 6]).unwrap();
+    let json = serde_json::to_string(&grid).unwrap();
+    assert_eq!(json, r#"{"width":2,"height":3,"cells":[1,2,3,4,5,6]}"#);
+    let deserialized: DynamicGrid<i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, grid);
+}
+
+#[test]
+fn empty_grid_round_trips() {
+    let grid: DynamicGrid<i32> = DynamicGrid::new();
+    let json = serde_json::to_string(&grid).unwrap();
+    assert_eq!(
+        serde_json::from_str::<DynamicGrid<i32>>(&json).unwrap(),
+        grid
+    );
+}
+
+#[test]
+fn mismatched_cell_count_is_an_error() {
+    let result: Result<DynamicGrid<i32>, _> =
+        serde_json::from_str(r#"{"width":2,"height":3,"cells":[1,2,3,4,5]}"#);
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains("expected 6 cells"), "{error}");
+    let result: Result<DynamicGrid<i32>, _> = serde_json::from_str(r#"{"width":2,"cells":[]}"#);
+    assert!(result.is_err());
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Level {
+    #[serde(with = "cell_grid::serde_rows")]
+    tiles: DynamicGrid<char>,
+}
+
+#[test]
+fn grid_can_be_serialized_as_rows() {
+    let level = Level {
+        tiles: DynamicGrid::new_from_iter(3, "#.##.#".chars()).unwrap(),
+    };
+    let json = serde_json::to_string(&level).unwrap();
+    assert_eq!(json, r##"{"tiles":[["#",".","#"],["#",".","#"]]}"##);
+    let deserialized: Level = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.tiles, level.tiles);
+}
+
+#[test]
+fn ragged_rows_are_an_error() {
+    let result: Result<Level, _> = serde_json::from_str(r#"{"tiles":[["a","b"],["c"]]}"#);
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains("row 1 has 1 cells"), "{error}");
+}
+
+#[test]
+#[allow(deprecated)]
+fn legacy_types_round_trip() {
+    use cell_grid::{Coord, Grid, Rect};
+    let coord = Coord::new(1, -2);
+    let json = serde_json::to_string(&coord).unwrap();
+    assert_eq!(json, r#"{"x":1,"y":-2}"#);
+    assert_eq!(serde_json::from_str::<Coord>(&json).unwrap(), coord);
+    let rect = Rect::from_min_max([0, 0], [2, 3]);
+    let json = serde_json::to_string(&rect).unwrap();
+    assert_eq!(serde_json::from_str::<Rect>(&json).unwrap(), rect);
+    let grid = Grid::from_row_major_iter(2, [1, 2, 3, 4]);
+    let json = serde_json::to_string(&grid).unwrap();
+    assert_eq!(serde_json::from_str::<Grid<i32>>(&json).unwrap(), grid);
+}
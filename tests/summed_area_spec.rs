@@ -0,0 +1,56 @@
+#![allow(missing_docs)]
+
+use cell_grid::{DynamicGrid, SummedAreaTable};
+use rstest::rstest;
+
+fn grid() -> DynamicGrid<i32> {
+    DynamicGrid::new_with(5, 4, |x, y| i32::try_from(x * 3 + y * 7 % 4).unwrap() - 4)
+}
+
+#[rstest]
+#[case(0, 0, 5, 4)]
+#[case(1, 2, 3, 1)]
+#[case(3, 1, 10, 10)]
+#[case(2, 2, 0, 2)]
+#[case(4, 3, 1, 1)]
+fn sums_match_cells_in_rect(
+    #[case] x: usize,
+    #[case] y: usize,
+    #[case] width: usize,
+    #[case] height: usize,
+) {
+    let grid = grid();
+    let table: SummedAreaTable<i64> = grid.summed_area_table();
+    let expected: i64 = grid
+        .cells_in_rect(x, y, width, height)
+        .map(|c| i64::from(*c))
+        .sum();
+    assert_eq!(table.sum_in_rect(x, y, width, height), expected);
+}
+
+#[test]
+fn squared_sums_match_cells_in_rect() {
+    let grid = grid();
+    let table: SummedAreaTable<i64> = grid.squared_summed_area_table();
+    let expected: i64 = grid
+        .cells_in_rect(1, 1, 3, 2)
+        .map(|c| i64::from(*c * *c))
+        .sum();
+    assert_eq!(table.sum_in_rect(1, 1, 3, 2), expected);
+}
+
+#[test]
+fn unsigned_sums_do_not_underflow() {
+    let grid = DynamicGrid::new_with(4, 4, |_, _| 255_u8);
+    let table: SummedAreaTable<u32> = grid.summed_area_table();
+    assert_eq!(table.sum_in_rect(1, 1, 2, 2), 4 * 255);
+}
+
+#[test]
+fn rect_outside_of_the_grid_is_empty() {
+    let table: SummedAreaTable<i64> = grid().summed_area_table();
+    assert_eq!(table.sum_in_rect(7, 0, 2, 2), 0);
+    assert_eq!(table.sum_in_rect(0, 7, 2, 2), 0);
+    let empty: SummedAreaTable<i64> = DynamicGrid::<i32>::new().summed_area_table();
+    assert_eq!(empty.sum_in_rect(0, 0, 3, 3), 0);
+}
@@ -0,0 +1,92 @@
+#![allow(missing_docs)]
+
+use cell_grid::{
+    terminal::{IncrementalRenderer, TerminalCell},
+    DynamicGrid,
+};
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn plain(c: &char) -> TerminalCell {
+    TerminalCell::new(*c)
+}
+
+#[test]
+fn ansi_without_colors_is_plain_text() {
+    let grid = DynamicGrid::new_from_iter(3, "ab.cd.".chars()).unwrap();
+    assert_eq!(grid.to_ansi(plain), "ab.\ncd.\n");
+    assert_eq!(DynamicGrid::<char>::new().to_ansi(plain), "");
+}
+
+#[test]
+fn ansi_only_changes_colors_when_needed() {
+    let grid = DynamicGrid::new_from_iter(4, "##.#".chars()).unwrap();
+    let ansi = grid.to_ansi(|c| {
+        if *c == '#' {
+            TerminalCell::new('#')
+                .foreground([255, 0, 0])
+                .background([0, 0, 1])
+        } else {
+            TerminalCell::new(' ')
+        }
+    });
+    assert_eq!(
+        ansi,
+        "\x1b[0;38;2;255;0;0;48;2;0;0;1m##\x1b[0m \x1b[0;38;2;255;0;0;48;2;0;0;1m#\x1b[0m\n"
+    );
+}
+
+#[test]
+fn braille_pads_incomplete_characters() {
+    let grid = DynamicGrid::new_from_iter(3, [true; 3]).unwrap();
+    assert_eq!(grid.to_braille(), "⠉⠁\n");
+    let full = DynamicGrid::new_with(2, 4, |_, _| true);
+    assert_eq!(full.to_braille(), "⣿\n");
+    assert_eq!(DynamicGrid::<bool>::new().to_braille(), "");
+}
+
+#[test]
+fn first_frame_clears_and_draws_everything() {
+    let grid = DynamicGrid::new_from_iter(2, "abcd".chars()).unwrap();
+    let mut renderer = IncrementalRenderer::new();
+    assert_eq!(
+        renderer.render(&grid, plain),
+        "\x1b[2J\x1b[1;1Hab\x1b[2;1Hcd"
+    );
+}
+
+#[test]
+fn next_frames_only_draw_changed_cells() {
+    let mut grid = DynamicGrid::new_from_iter(3, "abcdef".chars()).unwrap();
+    let mut renderer = IncrementalRenderer::new();
+    let _ = renderer.render(&grid, plain);
+    grid.set(1, 0, 'x');
+    grid.set(2, 0, 'y');
+    grid.set(0, 1, 'z');
+    assert_eq!(renderer.render(&grid, plain), "\x1b[1;2Hxy\x1b[2;1Hz");
+    assert_eq!(renderer.render(&grid, plain), "");
+}
+
+#[test]
+fn color_changes_are_redrawn() {
+    let grid = DynamicGrid::new_from_iter(1, ['a']).unwrap();
+    let mut renderer = IncrementalRenderer::new();
+    let _ = renderer.render(&grid, plain);
+    assert_eq!(
+        renderer.render(&grid, |c| TerminalCell::new(*c).background([1, 2, 3])),
+        "\x1b[1;1H\x1b[0;48;2;1;2;3ma\x1b[0m"
+    );
+    assert_eq!(renderer.render(&grid, plain), "\x1b[1;1Ha");
+}
+
+#[test]
+fn resized_or_reset_frames_are_fully_redrawn() {
+    let mut renderer = IncrementalRenderer::new();
+    let _ = renderer.render(&DynamicGrid::new_from_iter(2, "ab".chars()).unwrap(), plain);
+    let resized = DynamicGrid::new_from_iter(1, "ab".chars()).unwrap();
+    assert_eq!(
+        renderer.render(&resized, plain),
+        "\x1b[2J\x1b[1;1Ha\x1b[2;1Hb"
+    );
+    renderer.reset();
+    assert!(renderer.render(&resized, plain).starts_with("\x1b[2J"));
+}
@@ -0,0 +1,60 @@
+#![allow(missing_docs)]
+
+use cell_grid::DynamicGrid;
+
+const MAP: &str = "
+#####
+#..@#
+#####
+";
+
+#[test]
+fn text_round_trips() {
+    let grid = DynamicGrid::parse_text(MAP, |c| c).unwrap();
+    assert_eq!(grid.width(), 5);
+    assert_eq!(grid.height(), 3);
+    assert_eq!(grid.get(3, 1), Some(&'@'));
+    assert_eq!(grid.display_with(|c| *c).to_string(), MAP.trim());
+}
+
+#[test]
+fn ragged_lines_report_their_line_number() {
+    let error = DynamicGrid::parse_text("\n###\n###\n##\n###", |c| c).unwrap_err();
+    assert_eq!(error.line(), Some(4));
+    assert!(error.to_string().contains("line 4"));
+}
+
+#[test]
+fn push_row_errors_have_no_line() {
+    let mut grid = DynamicGrid::new_from_iter(2, [1, 2]).unwrap();
+    assert_eq!(grid.push_row([1]).unwrap_err().line(), None);
+}
+
+#[test]
+fn empty_text_is_an_empty_grid() {
+    let grid = DynamicGrid::parse_text("\n\n", |c| c).unwrap();
+    assert!(grid.is_empty());
+    assert_eq!(grid.display_with(|c| *c).to_string(), "");
+    assert_eq!(
+        grid.display_with(|c| *c).with_border().to_string(),
+        "┌┐\n└┘"
+    );
+}
+
+#[test]
+fn border_surrounds_the_cells() {
+    let grid = DynamicGrid::parse_text("ab\ncd", |c| c.to_ascii_uppercase()).unwrap();
+    assert_eq!(
+        grid.display_with(|c| *c).with_border().to_string(),
+        "┌──┐\n│AB│\n│CD│\n└──┘"
+    );
+}
+
+#[test]
+fn aligned_columns_are_right_aligned() {
+    let grid = DynamicGrid::new_from_iter(3, [1, -20, 3, 400, 5, 6]).unwrap();
+    assert_eq!(
+        grid.display_with(|c| *c).align_columns().to_string(),
+        "  1 -20 3\n400   5 6"
+    );
+}
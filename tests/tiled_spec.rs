@@ -0,0 +1,205 @@
+#![allow(missing_docs)]
+#![cfg(feature = "tiled")]
+
+use cell_grid::{
+    tiled::{DataEncoding, Map, TileId, TileLayer, Tileset},
+    DynamicGrid,
+};
+use rstest::rstest;
+
+fn gids(layer: &TileLayer) -> Vec<u32> {
+    layer
+        .tiles
+        .cells()
+        .map(|tile| tile.map_or(0, TileId::raw))
+        .collect()
+}
+
+fn sample_map() -> Map {
+    let mut map = Map::new(3, 2, 16, 16);
+    map.tilesets.push(Tileset::new(1, "terrain.tsx"));
+    map.tilesets.push(Tileset::new(100, "props & <items>.tsx"));
+    let tiles =
+        DynamicGrid::new_from_iter(3, [1, 0, 101, 2, 3, 0].into_iter().map(TileId::new)).unwrap();
+    map.layers.push(TileLayer::new("Ground", tiles));
+    let flipped = TileId::new(5)
+        .unwrap()
+        .with_horizontal_flip(true)
+        .with_diagonal_flip(true);
+    let tiles = DynamicGrid::new_with(3, 2, |x, y| (x == y).then(|| flipped));
+    map.layers.push(TileLayer::new("\"Decorations\"", tiles));
+    map
+}
+
+#[test]
+fn tile_id_has_flip_flags() {
+    let tile = TileId::from_raw(0x8000_0003 | 0x4000_0000).unwrap();
+    assert_eq!(tile.gid(), 3);
+    assert!(tile.is_flipped_horizontally());
+    assert!(tile.is_flipped_vertically());
+    assert!(!tile.is_flipped_diagonally());
+    assert_eq!(tile.with_vertical_flip(false).raw(), 0x8000_0003);
+    assert_eq!(TileId::from_raw(0), None);
+    assert_eq!(TileId::from_raw(0x8000_0000), None);
+    assert_eq!(TileId::new(0x8000_0003), None);
+}
+
+#[test]
+fn finds_the_tileset_of_a_tile() {
+    let map = sample_map();
+    let (tileset, local_id) = map.tileset_of(TileId::new(101).unwrap()).unwrap();
+    assert_eq!((tileset.first_gid, local_id), (100, 1));
+    let (tileset, local_id) = map.tileset_of(TileId::new(99).unwrap()).unwrap();
+    assert_eq!((tileset.first_gid, local_id), (1, 98));
+}
+
+#[rstest]
+fn tmx_round_trips(
+    #[values(
+        DataEncoding::Csv,
+        DataEncoding::Base64,
+        DataEncoding::Base64Zlib,
+        DataEncoding::Base64Gzip
+    )]
+    encoding: DataEncoding,
+) {
+    let map = sample_map();
+    assert_eq!(Map::from_tmx(&map.to_tmx(encoding)).unwrap(), map);
+}
+
+#[rstest]
+fn json_round_trips(
+    #[values(
+        DataEncoding::Csv,
+        DataEncoding::Base64,
+        DataEncoding::Base64Zlib,
+        DataEncoding::Base64Gzip
+    )]
+    encoding: DataEncoding,
+) {
+    let map = sample_map();
+    assert_eq!(Map::from_json(&map.to_json(encoding)).unwrap(), map);
+}
+
+#[test]
+fn infinite_maps_round_trip() {
+    let mut map = sample_map();
+    map.infinite = true;
+    map.layers[0].x = -16;
+    map.layers[0].y = 32;
+    assert_eq!(Map::from_tmx(&map.to_tmx(DataEncoding::Csv)).unwrap(), map);
+    assert_eq!(
+        Map::from_json(&map.to_json(DataEncoding::Base64Zlib)).unwrap(),
+        map
+    );
+}
+
+#[test]
+fn reads_tmx_tiles_and_groups() {
+    let tmx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="2" height="1" tilewidth="8" tileheight="8" infinite="0">
+ <tileset firstgid="1" name="embedded" tilewidth="8" tileheight="8" tilecount="4" columns="2">
+  <image source="tiles.png" width="16" height="16"/>
+ </tileset>
+ <group id="3" name="group">
+  <layer id="1" name="xml" width="2" height="1">
+   <data><tile gid="4"/><tile/></data>
+  </layer>
+  <objectgroup id="2" name="objects"/>
+ </group>
+</map>"#;
+    let map = Map::from_tmx(tmx).unwrap();
+    assert_eq!(map.tilesets[0].name.as_deref(), Some("embedded"));
+    assert_eq!(map.tilesets[0].source, None);
+    assert_eq!(map.layers.len(), 1);
+    assert_eq!(map.layers[0].name, "xml");
+    assert_eq!(gids(&map.layers[0]), [4, 0]);
+}
+
+#[test]
+fn reads_infinite_tmx_chunks() {
+    let tmx = r#"<map orientation="orthogonal" width="10" height="10" tilewidth="8" tileheight="8" infinite="1">
+ <layer id="1" name="chunks" width="4" height="3">
+  <data encoding="csv">
+   <chunk x="-2" y="0" width="2" height="1">1,2</chunk>
+   <chunk x="0" y="1" width="2" height="2">3,4,
+5,6</chunk>
+  </data>
+ </layer>
+</map>"#;
+    let map = Map::from_tmx(tmx).unwrap();
+    let layer = &map.layers[0];
+    assert_eq!((layer.x, layer.y), (-2, 0));
+    assert_eq!(layer.tiles.width(), 4);
+    assert_eq!(gids(layer), [1, 2, 0, 0, 0, 0, 3, 4, 0, 0, 5, 6]);
+}
+
+#[test]
+fn reads_json_base64_data() {
+    // Gids [1, 2] as little-endian bytes: AQAAAAIAAAA=
+    let json = r#"{
+        "orientation": "orthogonal", "width": 2, "height": 1, "tilewidth": 8, "tileheight": 8, "infinite": false,
+        "tilesets": [{ "firstgid": 1, "source": "tiles.tsj" }],
+        "layers": [
+            { "type": "group", "layers": [
+                { "type": "tilelayer", "name": "a", "width": 2, "height": 1, "encoding": "base64", "compression": "", "data": "AQAAAAIAAAA=" }
+            ]},
+            { "type": "objectgroup", "name": "objects", "objects": [] }
+        ]
+    }"#;
+    let map = Map::from_json(json).unwrap();
+    assert_eq!(map.layers.len(), 1);
+    assert_eq!(gids(&map.layers[0]), [1, 2]);
+}
+
+#[rstest]
+#[case::not_xml("not xml")]
+#[case::not_a_map(r#"<tileset firstgid="1"/>"#)]
+#[case::isometric(
+    r#"<map orientation="isometric" width="1" height="1" tilewidth="8" tileheight="8"/>"#
+)]
+#[case::wrong_size(
+    r#"<map width="2" height="1" tilewidth="8" tileheight="8"><layer name="a" width="2" height="1"><data encoding="csv">1</data></layer></map>"#
+)]
+#[case::invalid_gid(
+    r#"<map width="1" height="1" tilewidth="8" tileheight="8"><layer name="a" width="1" height="1"><data encoding="csv">x</data></layer></map>"#
+)]
+#[case::zstd(
+    r#"<map width="1" height="1" tilewidth="8" tileheight="8"><layer name="a" width="1" height="1"><data encoding="base64" compression="zstd">AQAAAA==</data></layer></map>"#
+)]
+#[case::invalid_zlib(
+    r#"<map width="1" height="1" tilewidth="8" tileheight="8"><layer name="a" width="1" height="1"><data encoding="base64" compression="zlib">AQAAAA==</data></layer></map>"#
+)]
+#[case::zlib_too_large(
+    r#"<map width="1" height="1" tilewidth="8" tileheight="8"><layer name="a" width="1" height="1"><data encoding="base64" compression="zlib">eJztwQENAAAAwqD3T20PBxQAAADwbhAAAAE=</data></layer></map>"#
+)]
+#[case::gzip_too_large(
+    r#"<map width="1" height="1" tilewidth="8" tileheight="8"><layer name="a" width="1" height="1"><data encoding="base64" compression="gzip">H4sIAAAAAAACA+3BAQ0AAADCoPdPbQ8HFAAAAPBuEQAcxwAQAAA=</data></layer></map>"#
+)]
+#[case::huge_layer(
+    r#"<map width="1" height="1" tilewidth="8" tileheight="8"><layer name="a" width="4294967296" height="4294967296"><data encoding="csv">1</data></layer></map>"#
+)]
+#[case::chunks_too_far_apart(
+    r#"<map width="1" height="1" tilewidth="8" tileheight="8" infinite="1"><layer name="a" width="1" height="1"><data encoding="csv">
+    <chunk x="-2147483648" y="-2147483648" width="1" height="1">1</chunk>
+    <chunk x="2147483646" y="2147483646" width="1" height="1">1</chunk>
+    </data></layer></map>"#
+)]
+#[case::chunks_area_too_large(
+    r#"<map width="1" height="1" tilewidth="8" tileheight="8" infinite="1"><layer name="a" width="1" height="1"><data encoding="csv">
+    <chunk x="0" y="0" width="1" height="1">1</chunk>
+    <chunk x="100000" y="100000" width="1" height="1">1</chunk>
+    </data></layer></map>"#
+)]
+fn invalid_tmx_is_an_error(#[case] tmx: &str) {
+    assert!(Map::from_tmx(tmx).is_err());
+}
+
+#[test]
+fn invalid_json_is_an_error() {
+    assert!(Map::from_json("{").is_err());
+    assert!(Map::from_json(r#"{"width": 1}"#).is_err());
+    let wrong_size = r#"{"width": 1, "height": 1, "tilewidth": 8, "tileheight": 8,
+        "layers": [{ "type": "tilelayer", "width": 1, "height": 1, "data": [1, 2] }]}"#;
+    assert!(Map::from_json(wrong_size).is_err());
+}